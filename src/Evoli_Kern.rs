@@ -1,54 +1,165 @@
 // src/Evoli_Kern.rs - Erweiterter Kern mit Internetzugang und offener Evolution
-use std::fs::{self, File};
+use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use std::time::{Duration, Instant};
-use std::collections::HashMap;
-use rand::{Rng, thread_rng};
-use reqwest;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
-pub const MAX_STORAGE_BYTES: u64 = 1_099_511_627_776; // 1 TB in Bytes
+mod ast_mutation;
+mod build_sandbox;
+mod capabilities;
+mod checkpoint;
+mod config;
+mod content_store;
+mod crawler;
+mod crossover;
+mod diagnostics;
+mod eviction;
+mod execution_sandbox;
+mod fitness;
+mod genome_store;
+mod http_cache;
+mod metrics_exporter;
+mod pareto;
+mod population;
+mod safety;
+mod seeded_rng;
+mod storage_accounting;
+mod system_metrics;
+mod transport;
+pub use ast_mutation::{
+    structural_operators, BinaryOperatorMutation, ItemReorderMutation, LiteralMutation,
+    MatchArmMutation, SwapStatementMutation,
+};
+pub use build_sandbox::{BuildResult, BuildSandbox, TestOutcome};
+pub use capabilities::{Capability, CapabilityEscalation, CapabilityManifest, NoCapabilityEscalationRule};
+pub use checkpoint::{KernelCheckpoint, CHECKPOINT_VERSION};
+pub use config::{
+    ConfigChange, EvoliConfig, EvolutionConfig, InterfaceConfig, LearningConfig, PathsConfig, StorageConfig,
+    DEFAULT_CONFIG_FILE,
+};
+pub use content_store::{content_hash, ContentEntry, ContentStore, PutOutcome};
+pub use crawler::{
+    extract_links, host_of, normalize_url, robots_url, CrawlConfig, CrawlFrontier, FrontierEntry, HostState, RobotsRules,
+    CRAWLER_AGENT, ROBOTS_RETRY_SECS, ROBOTS_TTL_SECS,
+};
+pub use crossover::{crossover_operators, ItemCrossover, SubtreeCrossover};
+pub use diagnostics::{
+    apply_suggestions, parse_cargo_messages, CandidateReport, Diagnostic, DiagnosticLevel,
+    DiagnosticSpan, DiagnosticStats, RepairMutationStrategy, Suggestion,
+};
+pub use eviction::{
+    collect_entries, wildcard_match, AccessLedger, AccessRecord, EvictionCandidate, EvictionItem, EvictionPlan, EvictionPolicy,
+    EvictionReason, EVICTABLE_CATEGORIES,
+};
+pub use execution_sandbox::{probe_network_isolation, ExecutionLimits, ExecutionResult, ExecutionSandbox};
+pub use fitness::{
+    aggregate_fitness, default_evaluators, default_objectives, BenchmarkRuntimeEvaluator,
+    BinarySizeEvaluator, CompileTimeEvaluator, CompilesCleanlyEvaluator, GenomeLengthEvaluator,
+    TestPassRatioEvaluator, WarningCountEvaluator,
+};
+pub use http_cache::{cacheable, parse_http_date, HttpCacheInfo, HEURISTIC_FRESHNESS_LIMIT_SECS};
+pub use genome_store::{genome_id, line_diff, GenomeId, GenomeRecord, GenomeStore, HistoryEvent, HistoryEventKind};
+pub use metrics_exporter::{
+    collect_metrics, encode_openmetrics, render_openmetrics, serve_metrics, MetricFamily, MetricKind,
+    OPENMETRICS_CONTENT_TYPE,
+};
+pub use pareto::{
+    crowding_distance, dominates, evaluate_objectives, non_dominated_sort, ArchiveEntry,
+    ObjectiveVector, ParetoArchive,
+};
+pub use population::{Individual, Population, PopulationConfig, ReplacementStrategy, SelectionMethod};
+pub use seeded_rng::{derive_rng, random_seed, EvoliRng};
+pub use storage_accounting::{CategoryUsage, StorageAccountant, StorageCategory, StorageRoot, StorageUsage};
+pub use system_metrics::{SystemMonitor, SystemSample};
+pub use transport::{
+    transport_for, HttpTransport, ReplayTransport, Transport, TransportError, TransportFuture, TransportResponse,
+};
+pub use safety::{
+    configured_rules, default_rules, domain_allowed, AllowedDomainsRule, BackupBeforeMutationRule, ControlledResourceUsageRule, NoSystemHarmRule,
+    PolicyAction, PolicyEngine, PolicyViolation, SafetyRule, ValidateCompilabilityRule,
+};
+
+pub const MAX_STORAGE_BYTES: u64 = 1_099_511_627_776; // 1 TB in Bytes, Standard für `storage.max_bytes`
 
 /// Der erweiterte evolutionäre Kern von Evoli-KI
 pub struct EnhancedEvoliKern {
+    // Konfiguration (Pfade, Limits, Intervalle, Wahrscheinlichkeiten)
+    pub config: EvoliConfig,
+    
     // Genome - mehrere Versionen des eigenen Quellcodes
     pub primary_genome: String,           // Hauptcode
     pub module_genomes: HashMap<String, String>, // Zusatzmodule
     
+    // Population - das beste Individuum ist stets `primary_genome`
+    pub population: Population,
+    
     // Evolutionsdaten
     pub generation: u64,
     pub fitness_score: f64,
+    pub incumbent_evaluated: bool,        // Wurde `primary_genome` bereits gemessen?
+    build_cache_warm: bool,               // Abhängigkeiten in diesem Prozess bereits gebaut?
     pub creation_time: Instant,
+    pub seed: u64,                        // Seed des Laufs, siehe `seeded_rng`
+    rng: EvoliRng,                        // Strom des laufenden Zyklus
     
     // Ressourcennutzung und Metriken
-    pub memory_usage: usize,
-    pub cpu_usage: f64,
-    pub disk_usage: u64,
+    pub memory_usage: usize,              // RSS des eigenen Prozesses in Bytes
+    pub cpu_usage: f64,                   // CPU-Anteil des eigenen Prozesses in Prozent
+    pub disk_usage: u64,                  // Alle Daten der Instanz, siehe `storage_usage()`
+    pub storage: StorageAccountant,       // Belegung je Kategorie, inkrementell erfasst
+    pub access_ledger: AccessLedger,      // Zugriffe auf Cache- und Wissensdateien für die Bereinigung
+    pub system_monitor: SystemMonitor,    // Zeitreihe der Prozess- und Hostmetriken
+    live_metrics: RuntimeMetrics,         // Fortlaufende Zähler, siehe `metrics()`
     
     // Wissensbasis und Speicher
     pub knowledge_dir: PathBuf,           // Verzeichnis zur Datenspeicherung
     pub internet_cache: PathBuf,          // Zwischenspeicher für Internet-Daten
+    pub knowledge_store: ContentStore,    // Extrahierte Code-Beispiele unter `knowledge_dir`
+    pub cache_store: ContentStore,        // Abgerufene Seiten unter `internet_cache`, je URL
+    pub state_dir: PathBuf,               // Persistenter Zustand des Kerns (Archive etc.)
     
     // Evolutionsstrategien - dynamisch anpassbar
     pub mutation_strategies: Vec<Box<dyn MutationStrategy>>,
+    pub developed_strategies: Vec<String>, // Namen der selbstentwickelten Mutationsstrategien
+    pub crossover_strategies: Vec<Box<dyn CrossoverStrategy>>,
     pub fitness_evaluators: Vec<Box<dyn FitnessEvaluator>>,
+    pub fitness_weights: HashMap<String, f64>, // Gewicht je Evaluator-Name (Standard 1.0)
+    pub objective_evaluators: Vec<Box<dyn FitnessEvaluator>>, // Ziele der Mehrzieloptimierung
+    pub pareto_archive: ParetoArchive,
     
     // Selbstmodifikationsregeln - können zur Laufzeit erweitert werden
     pub modification_rules: Vec<String>,
     
+    // Isolierter Build von Kandidaten
+    pub build_sandbox: Arc<BuildSandbox>,
+    pub test_isolation_problem: Option<String>, // Warum Kandidaten-Tests abgeschaltet sind
+    pub diagnostic_stats: DiagnosticStats, // Compiler-Diagnosen aller Kandidaten-Builds
+    
     // Internet-Zugriffsstatus
     pub internet_enabled: bool,
     pub last_internet_access: Instant,
+    pub transport: Box<dyn Transport>,    // Zugang zum Netz (`learning.transport`)
+    pub frontier: CrawlFrontier,          // Warteschlange des Crawlers, gestartet aus `learning.urls`
     
     // Sicherheitsmaßnahmen
-    pub safety_interlocks: Vec<String>,
-    pub evolution_backups: Vec<(u64, String)>, // (Generation, Code-Backup)
+    pub safety_interlocks: PolicyEngine,  // Wird vor Übernahme, Schreib- und Netzwerkzugriffen geprüft
+    pub genome_store: GenomeStore,        // Alle Genome mit Abstammung, Fitness und Kompilierergebnis
 }
 
-/// Trait für verschiedene Mutationsstrategien
+/// Trait für verschiedene Mutationsstrategien. Alle Zufallsentscheidungen ziehen
+/// aus `rng`, damit ein Lauf mit gleichem Seed reproduzierbar bleibt.
 pub trait MutationStrategy: Send + Sync {
-    fn mutate(&self, code: &str) -> String;
+    fn mutate(&self, code: &str, rng: &mut EvoliRng) -> String;
+    fn name(&self) -> String;
+}
+
+/// Trait für Rekombination zweier Genome
+pub trait CrossoverStrategy: Send + Sync {
+    fn crossover(&self, first: &str, second: &str, rng: &mut EvoliRng) -> String;
     fn name(&self) -> String;
 }
 
@@ -58,45 +169,83 @@ pub trait FitnessEvaluator: Send + Sync {
     fn name(&self) -> String;
 }
 
-/// Laufzeitmetriken zur Leistungsmessung
+/// Laufzeitmetriken zur Leistungsmessung. Die Zähler werden vom Kern über seine
+/// gesamte Lebensdauer (auch über Neustarts hinweg) fortgeschrieben.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RuntimeMetrics {
-    pub memory_usage: usize,
-    pub cpu_usage: f64,
+    pub memory_usage: usize,               // RSS in Bytes
+    pub cpu_usage: f64,                    // CPU-Anteil in Prozent
     pub disk_usage: u64,
+    pub load_average: [f64; 3],
+    pub free_disk_bytes: u64,              // Auf dem Datenträger der Wissensbasis
+    pub open_file_descriptors: Option<u64>,
+    pub system_samples: Vec<SystemSample>, // Letzte Messungen, älteste zuerst
     pub uptime: Duration,
     pub successful_compilations: u64,
     pub failed_compilations: u64,
+    pub adopted_genomes: u64,
     pub internet_requests: u64,
-    pub knowledge_items: u64,
+    pub failed_internet_requests: u64,
+    pub bytes_downloaded: u64,
+    pub cache_hits: u64,                   // Seiten aus dem Cache statt aus dem Netz (frisch, offline, Ausfall)
+    pub not_modified: u64,                 // Erfolgreiche Revalidierungen (304)
+    pub knowledge_items: u64,              // Gespeicherte Code-Beispiele
+    pub storage_cleanups: u64,
+    pub files_evicted: u64,
+    pub bytes_evicted: u64,
+    pub strategy_outcomes: BTreeMap<String, StrategyOutcome>, // Je Operator (Teil von `origin`)
+    
+    // Messwerte des zuletzt gebauten Kandidaten
+    pub compile_success: bool,
+    pub warning_count: u64,
+    pub binary_size: u64,
+    pub compile_time: Duration,
+    pub tests_passed: u64,
+    pub tests_failed: u64,
+    pub benchmark_runtime: Option<Duration>,
+    pub execution: Option<ExecutionResult>, // Ausführung der Test-Binärdatei in der Sandbox
+}
+
+/// Ergebnisse aller Kandidaten, an denen ein Operator beteiligt war
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StrategyOutcome {
+    pub attempts: u64, // Gebaute Kandidaten
+    pub compiled: u64,
+    pub improved: u64, // Fitness über der des Elternteils
+}
+
+impl StrategyOutcome {
+    /// Anteil kompilierbarer Kandidaten
+    pub fn success_rate(&self) -> f64 {
+        if self.attempts == 0 { 0.0 } else { self.compiled as f64 / self.attempts as f64 }
+    }
 }
 
 /// Implementierung grundlegender Mutationsstrategien
-struct BasicMutationStrategy;
+struct BasicMutationStrategy {
+    point_mutation_rate: f64,
+}
 impl MutationStrategy for BasicMutationStrategy {
-    fn mutate(&self, code: &str) -> String {
+    fn mutate(&self, code: &str, rng: &mut EvoliRng) -> String {
         // Grundlegende Mutationen wie zuvor
-        let mut rng = thread_rng();
         let new_code = code.to_string();
         
         // Zufällige Punktmutationen
-        if rng.gen::<f64>() < 0.3 {
+        if rng.gen::<f64>() < self.point_mutation_rate {
             let lines: Vec<&str> = new_code.lines().collect();
             if !lines.is_empty() {
                 let target_line = rng.gen_range(0..lines.len());
                 
-                // Verschiedene Mutationsarten
-                match rng.gen_range(0..5) {
-                    0 => {
-                        // Kommentarmutation
-                        let comment = format!("// Evolutionär optimiert - Gen {}", chrono::Local::now());
-                        let modified_code = new_code.replace(
-                            lines[target_line], 
-                            &format!("{}\n{}", comment, lines[target_line])
-                        );
-                        return modified_code;
-                    },
-                    // Weitere Mutationsarten wie zuvor...
-                    _ => {}
+                // Kommentarmutation (strukturelle Mutationen siehe AdvancedMutationStrategy)
+                if rng.gen_range(0..5) == 0 {
+                    // Markierung aus dem Zufallsstrom statt Zeitstempel, damit der Lauf reproduzierbar bleibt
+                    let comment = format!("// Evolutionär optimiert - Gen {:08x}", rng.gen::<u32>());
+                    let modified_code = new_code.replace(
+                        lines[target_line], 
+                        &format!("{}\n{}", comment, lines[target_line])
+                    );
+                    return modified_code;
                 }
             }
         }
@@ -109,31 +258,13 @@ impl MutationStrategy for BasicMutationStrategy {
     }
 }
 
-/// Fortgeschrittene Mutationsstrategie
-struct AdvancedMutationStrategy;
-impl MutationStrategy for AdvancedMutationStrategy {
-    fn mutate(&self, code: &str) -> String {
-        // Komplexere Mutationen, die Strukturen und Funktionen verändern können
-        let new_code = code.to_string();
-        
-        // Strukturelle Mutationen (z.B. Funktionen vertauschen)
-        // und Parametermutationen (hier nur Platzhalter)
-        
-        new_code
-    }
-    
-    fn name(&self) -> String {
-        "AdvancedMutation".to_string()
-    }
-}
-
 /// Selbstentwickelte Mutationsstrategie (Platzhalter - würde von der KI entwickelt)
 struct SelfDevelopedMutationStrategy {
     name: String,
 }
 
 impl MutationStrategy for SelfDevelopedMutationStrategy {
-    fn mutate(&self, code: &str) -> String {
+    fn mutate(&self, code: &str, _rng: &mut EvoliRng) -> String {
         // Dieser Code würde dynamisch generiert und evaluiert werden
         // In einer echten Implementierung würde hier eine Art Interpreter oder
         // dynamische Codeausführung stattfinden
@@ -147,60 +278,164 @@ impl MutationStrategy for SelfDevelopedMutationStrategy {
     }
 }
 
+/// Inhalt und Content-Type einer Seite, aus dem Netz oder dem Cache
+type Page = (String, Option<String>);
+
 /// Implementierung der Kern-Funktionen
 impl EnhancedEvoliKern {
-    /// Erzeugt eine neue Instanz des erweiterten Kerns
+    /// Erzeugt eine neue Instanz des erweiterten Kerns mit den Standardwerten
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_config(EvoliConfig::default())
+    }
+    
+    /// Erzeugt eine neue Instanz des erweiterten Kerns; der Netzzugang richtet sich
+    /// nach `learning.transport`
+    pub fn with_config(config: EvoliConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let fixture_dir = config.paths.data_path(&config.learning.fixture_dir);
+        let transport = transport_for(&config.learning.transport, &fixture_dir);
+        Self::with_transport(config, transport)
+    }
+    
+    /// Erzeugt eine neue Instanz des erweiterten Kerns, die alle Abrufe über `transport` stellt
+    pub fn with_transport(config: EvoliConfig, transport: Box<dyn Transport>) -> Result<Self, Box<dyn std::error::Error>> {
         // Eigenen Quellcode laden
-        let primary_genome = fs::read_to_string("src/Evoli_Kern.rs")?;
+        let primary_genome = fs::read_to_string(config.paths.source_path())?;
         
         // Verzeichnisse für Wissensbasis und Cache erstellen
-        let knowledge_dir = PathBuf::from("evoli_knowledge");
-        let internet_cache = PathBuf::from("evoli_cache");
+        let knowledge_dir = config.paths.knowledge_path();
+        let internet_cache = config.paths.cache_path();
+        
+        let state_dir = config.paths.state_path();
         
         fs::create_dir_all(&knowledge_dir)?;
         fs::create_dir_all(&internet_cache)?;
+        fs::create_dir_all(&state_dir)?;
+        fs::create_dir_all(config.paths.evolved_path())?;
+        
+        // Prozess- und Hostmetriken der letzten 60 Messungen
+        let system_monitor = SystemMonitor::new(&knowledge_dir, 60);
         
         // Basis-Mutationsstrategien
-        let mut mutation_strategies: Vec<Box<dyn MutationStrategy>> = Vec::new();
-        mutation_strategies.push(Box::new(BasicMutationStrategy));
-        mutation_strategies.push(Box::new(AdvancedMutationStrategy));
+        let mut mutation_strategies: Vec<Box<dyn MutationStrategy>> = vec![
+            Box::new(BasicMutationStrategy { point_mutation_rate: config.evolution.point_mutation_rate }),
+        ];
+        
+        // Strukturelle Mutationsstrategien auf Basis des Syntaxbaums, einzeln registriert,
+        // damit Auswahl und Erfolgsstatistik je Operator gelten
+        mutation_strategies.extend(structural_operators());
         
         // Basis-Fitness-Evaluatoren
-        let fitness_evaluators: Vec<Box<dyn FitnessEvaluator>> = Vec::new();
-        
-        // Grundlegende Sicherheitsregeln
-        let safety_interlocks = vec![
-            "no_system_harm".to_string(),
-            "controlled_resource_usage".to_string(),
-            "backup_before_mutation".to_string(),
-            "validate_compilability".to_string(),
-        ];
+        let fitness_evaluators = default_evaluators();
+        let fitness_weights = config.evolution.fitness_weights.clone().into_iter().collect();
+        
+        // Ziele der Mehrzieloptimierung und persistentes Pareto-Archiv
+        let objective_evaluators = default_objectives();
+        let objective_names = objective_evaluators.iter().map(|e| e.name()).collect();
+        let pareto_archive = ParetoArchive::load_or_create(
+            &state_dir.join("pareto_archive.json"), objective_names, 32);
+        
+        // Grundlegende Sicherheitsregeln; Verstöße werden im Zustandsverzeichnis protokolliert.
+        // Die Fähigkeiten des Ausgangsgenoms bilden die erlaubte Basislinie für alle Kandidaten.
+        let mut rules = configured_rules(&config);
+        rules.push(Box::new(NoCapabilityEscalationRule::from_genome(&primary_genome)));
+        let safety_interlocks = PolicyEngine::new(rules, Some(state_dir.join("policy_violations.log")));
+        fs::create_dir_all(state_dir.join("quarantine"))?;
+        
+        // Inhaltsadressierter Speicher aller bisher erzeugten Genome
+        let genome_store = GenomeStore::open(&state_dir.join("genomes"));
+        
+        // Kandidaten werden in temporären Cargo-Projekten mit den Abhängigkeiten des Projekts gebaut
+        let mut build_sandbox = BuildSandbox::from_project(&config.paths.project_dir, &config.paths.evolved_path().join("target"))?;
+        
+        // Ohne eigenen Netzwerk-Namespace wird erzeugter Code gar nicht ausgeführt; alle
+        // Kandidaten (und das amtierende Genom) werden dann ohne Tests bewertet
+        let test_isolation_problem = probe_network_isolation().err();
+        if let Some(reason) = &test_isolation_problem {
+            println!("⚠️ Kandidaten-Tests abgeschaltet - Sandbox nicht verfügbar: {}", reason);
+            build_sandbox.run_tests = false;
+        }
+        let build_sandbox = Arc::new(build_sandbox);
+        
+        // Reparatur knapp gescheiterter Kandidaten anhand der Compiler-Vorschläge
+        mutation_strategies.push(Box::new(RepairMutationStrategy::new(build_sandbox.clone())));
+        
+        // Speicherbelegung aller Verzeichnisse der Instanz einschließlich der Build-Ausgaben
+        let storage = StorageAccountant::for_paths(&config.paths, &build_sandbox.target_dir);
+        let access_ledger = AccessLedger::load(&state_dir.join("access_ledger.json"));
+        
+        // Crawl-Frontier des letzten Laufs fortsetzen; neue Startseiten werden eingereiht,
+        // inzwischen nicht mehr erlaubte Domains verworfen
+        let mut frontier = CrawlFrontier::load(&state_dir.join("crawl_frontier.json"));
+        frontier.retain_domains(&config.learning.allowed_domains);
+        frontier.seed(&config.learning.urls);
+        
+        // Komprimierte, deduplizierte Ablage von Cache und Wissensbasis; lose Dateien
+        // früherer Versionen werden übernommen
+        let mut knowledge_store = ContentStore::open(&knowledge_dir);
+        let mut cache_store = ContentStore::open(&internet_cache);
+        let imported = knowledge_store.import_loose_files()? + cache_store.import_loose_files()?;
+        if imported > 0 {
+            println!("📦 {} Dateien in den komprimierten Speicher übernommen", imported);
+        }
+        
+        // Population aus dem eigenen Quellcode erzeugen
+        let population = Population::seed(&primary_genome, config.evolution.population.clone());
+        let internet_enabled = config.learning.enabled;
+        
+        // Ohne festen Seed wird einer gezogen und protokolliert, damit der Lauf wiederholbar bleibt
+        let seed = config.evolution.seed.unwrap_or_else(random_seed);
+        println!("🎲 Seed des Laufs: {}", seed);
         
         Ok(EnhancedEvoliKern {
+            config,
             primary_genome,
+            population,
             module_genomes: HashMap::new(),
             generation: 0,
             fitness_score: 0.0,
+            incumbent_evaluated: false,
+            build_cache_warm: false,
             creation_time: Instant::now(),
+            seed,
+            rng: derive_rng(seed, "evolution", 0),
             memory_usage: 0,
             cpu_usage: 0.0,
             disk_usage: 0,
+            storage,
+            access_ledger,
+            system_monitor,
+            live_metrics: RuntimeMetrics::default(),
             knowledge_dir,
             internet_cache,
+            knowledge_store,
+            cache_store,
+            state_dir,
             mutation_strategies,
+            developed_strategies: Vec::new(),
+            crossover_strategies: crossover_operators(),
             fitness_evaluators,
+            fitness_weights,
+            objective_evaluators,
+            pareto_archive,
             modification_rules: Vec::new(),
-            internet_enabled: true,
+            build_sandbox,
+            test_isolation_problem,
+            diagnostic_stats: DiagnosticStats::default(),
+            internet_enabled,
             last_internet_access: Instant::now(),
+            transport,
+            frontier,
             safety_interlocks,
-            evolution_backups: Vec::new(),
+            genome_store,
         })
     }
     
     /// Führt einen erweiterten Evolutionszyklus durch
     pub async fn run_evolution_cycle(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        println!("🧪 Starte erweiterten Evolutionszyklus (Generation {})", self.generation);
+        println!("🧪 Starte erweiterten Evolutionszyklus (Generation {}, Seed {})", self.generation, self.seed);
+        
+        // Jede Generation zieht aus einem eigenen Strom, auch nach einem Neustart
+        self.rng = derive_rng(self.seed, "evolution", self.generation);
         
         // 0. Backup erstellen
         self.create_backup()?;
@@ -215,6 +450,7 @@ impl EnhancedEvoliKern {
         
         // 3. Selbstmodifikation und Evolution durchführen
         self.evolve()?;
+        self.save_genome_store()?;
         
         // 4. Neue Evolutionsstrategien entwickeln
         self.develop_new_strategies()?;
@@ -225,139 +461,546 @@ impl EnhancedEvoliKern {
         // Generation erhöhen
         self.generation += 1;
         
+        // 6. Zustand für einen späteren Neustart sichern
+        self.save_checkpoint()?;
+        
         Ok(())
     }
     
-    /// Erstellt ein Backup des aktuellen Zustands
-    pub fn create_backup(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Speichere Code-Backup
-        self.evolution_backups.push((self.generation, self.primary_genome.clone()));
+    /// Setzt die Evolution mit dem zuletzt gespeicherten Checkpoint fort;
+    /// ohne Checkpoint entspricht dies `new()`
+    pub fn resume() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::resume_with(EvoliConfig::default())
+    }
+    
+    /// Wie `resume`, mit Pfaden und Parametern aus `config`
+    pub fn resume_with(config: EvoliConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let kern = Self::with_config(config)?;
+        Self::resume_kernel(kern)
+    }
+    
+    /// Wie `resume_with`, mit eigenem Netzzugang
+    pub fn resume_with_transport(config: EvoliConfig, transport: Box<dyn Transport>) -> Result<Self, Box<dyn std::error::Error>> {
+        let kern = Self::with_transport(config, transport)?;
+        Self::resume_kernel(kern)
+    }
+    
+    fn resume_kernel(mut kern: Self) -> Result<Self, Box<dyn std::error::Error>> {
+        
+        let path = kern.checkpoint_path();
+        if path.exists() {
+            let checkpoint = KernelCheckpoint::load(&path)?;
+            println!("♻️ Setze Evolution fort (Generation {}, gespeichert {})", checkpoint.generation, checkpoint.saved_at);
+            if let Some(seed) = checkpoint.seed.filter(|&seed| kern.config.evolution.seed.is_some_and(|s| s != seed)) {
+                println!("🎲 Seed {} aus dem Checkpoint ersetzt den konfigurierten Seed", seed);
+            }
+            kern.restore(checkpoint);
+            
+            // Parameter aus der Konfiguration haben Vorrang vor dem gespeicherten Stand
+            kern.population.config = kern.config.evolution.population.clone();
+            kern.fitness_weights.extend(kern.config.evolution.fitness_weights.clone());
+            kern.internet_enabled &= kern.config.learning.enabled;
+        } else {
+            println!("🆕 Kein Checkpoint gefunden - starte bei Generation 0");
+        }
+        
+        Ok(kern)
+    }
+    
+    /// Übernimmt eine neu geladene, bereits validierte Konfiguration ohne den Zustand
+    /// zu verlieren: Limits, Wahrscheinlichkeiten, Fitness-Gewichte, Population, Lernquellen und die davon
+    /// abhängigen Sicherheitsregeln. Pfade, Metrik-Adresse, Seed und Transport bleiben bis
+    /// zum Neustart unverändert. Liefert alle Unterschiede zur bisherigen Konfiguration.
+    pub fn apply_config(&mut self, mut config: EvoliConfig) -> Vec<ConfigChange> {
+        let changes = self.config.changes_to(&config);
+        config.paths = self.config.paths.clone();
+        config.interface.metrics_addr = self.config.interface.metrics_addr.clone();
+        config.evolution.seed = self.config.evolution.seed;
+        config.learning.transport = self.config.learning.transport.clone();
+        config.learning.fixture_dir = self.config.learning.fixture_dir.clone();
         
-        // Begrenze die Anzahl der Backups
-        if self.evolution_backups.len() > 10 {
-            self.evolution_backups.remove(0);
+        for rule in configured_rules(&config) {
+            self.safety_interlocks.replace_rule(rule);
+        }
+        for strategy in self.mutation_strategies.iter_mut().filter(|s| s.name() == "BasicMutation") {
+            *strategy = Box::new(BasicMutationStrategy { point_mutation_rate: config.evolution.point_mutation_rate });
+        }
+        self.population.config = config.evolution.population.clone();
+        for name in self.config.evolution.fitness_weights.keys() {
+            self.fitness_weights.remove(name);
+        }
+        self.fitness_weights.extend(config.evolution.fitness_weights.clone());
+        if config.learning.enabled != self.config.learning.enabled {
+            self.internet_enabled = config.learning.enabled;
         }
+        let dropped = self.frontier.retain_domains(&config.learning.allowed_domains);
+        if dropped > 0 {
+            println!("🧹 {} wartende URLs außerhalb der erlaubten Domains verworfen", dropped);
+        }
+        self.frontier.seed(&config.learning.urls);
         
-        // Physisches Backup in Datei
-        let backup_path = format!("evoli_backup_gen_{}.rs", self.generation);
-        fs::write(&backup_path, &self.primary_genome)?;
+        self.config = config;
+        changes
+    }
+    
+    /// Speicherort des Zustands-Checkpoints
+    pub fn checkpoint_path(&self) -> PathBuf {
+        self.state_dir.join("kernel_checkpoint.json")
+    }
+    
+    /// Momentaufnahme des gesamten Kernzustands
+    pub fn checkpoint(&self) -> KernelCheckpoint {
+        KernelCheckpoint {
+            version: CHECKPOINT_VERSION,
+            saved_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            generation: self.generation,
+            fitness_score: self.fitness_score,
+            incumbent_evaluated: self.incumbent_evaluated,
+            uptime: self.creation_time.elapsed(),
+            primary_genome: self.primary_genome.clone(),
+            module_genomes: self.module_genomes.clone(),
+            individuals: self.population.individuals.clone(),
+            population_config: self.population.config.clone(),
+            fitness_weights: self.fitness_weights.clone(),
+            modification_rules: self.modification_rules.clone(),
+            developed_strategies: self.developed_strategies.clone(),
+            diagnostic_stats: self.diagnostic_stats.clone(),
+            internet_enabled: self.internet_enabled,
+            metrics: self.live_metrics.clone(),
+            seed: Some(self.seed),
+        }
+    }
+    
+    /// Schreibt den Checkpoint, sofern die Sicherheitsregeln es erlauben
+    pub fn save_checkpoint(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let checkpoint = self.checkpoint();
+        let path = self.checkpoint_path();
+        let action = PolicyAction::WriteFile {
+            path: &path,
+            bytes: serde_json::to_vec(&checkpoint)?.len() as u64,
+            disk_usage: self.disk_usage,
+        };
+        if self.safety_interlocks.check(&action, self.generation).is_ok() {
+            checkpoint.save(&path)?;
+            println!("📌 Checkpoint gespeichert (Generation {})", self.generation);
+        }
+        Ok(())
+    }
+    
+    /// Übernimmt den Zustand eines Checkpoints
+    pub fn restore(&mut self, checkpoint: KernelCheckpoint) {
+        self.generation = checkpoint.generation;
+        self.fitness_score = checkpoint.fitness_score;
+        self.incumbent_evaluated = checkpoint.incumbent_evaluated;
+        self.creation_time = Instant::now().checked_sub(checkpoint.uptime).unwrap_or_else(Instant::now);
+        self.primary_genome = checkpoint.primary_genome;
+        self.module_genomes = checkpoint.module_genomes;
+        self.population = Population {
+            individuals: checkpoint.individuals,
+            config: checkpoint.population_config,
+        };
+        if self.population.is_empty() {
+            self.population = Population::seed(&self.primary_genome, self.population.config.clone());
+        }
+        self.fitness_weights = checkpoint.fitness_weights;
+        self.modification_rules = checkpoint.modification_rules;
+        for name in checkpoint.developed_strategies {
+            if !self.developed_strategies.contains(&name) {
+                self.mutation_strategies.push(Box::new(SelfDevelopedMutationStrategy { name: name.clone() }));
+                self.developed_strategies.push(name);
+            }
+        }
+        self.diagnostic_stats = checkpoint.diagnostic_stats;
+        self.internet_enabled = checkpoint.internet_enabled;
+        self.live_metrics = checkpoint.metrics;
+        if let Some(seed) = checkpoint.seed {
+            self.seed = seed;
+        }
+    }
+    
+    /// Sichert das aktuelle Genom im Genomspeicher
+    pub fn create_backup(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let id = self.genome_store.put(&self.primary_genome, &[], "Seed", self.generation);
+        if self.incumbent_evaluated {
+            self.genome_store.set_outcome(&id, self.fitness_score, true);
+        }
+        if self.genome_store.history.is_empty() {
+            self.genome_store.record_event(self.generation, HistoryEventKind::Seed, &id, self.fitness_score);
+        }
         
-        println!("💾 Backup erstellt: {}", backup_path);
+        if self.save_genome_store()? {
+            println!("💾 Backup erstellt: Genom {}", &id[..12]);
+        }
         Ok(())
     }
     
-    /// Analysiert die Ausführungsumgebung und Systemressourcen
-    pub fn analyze_environment(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Speichernutzung messen
-        self.memory_usage = std::mem::size_of::<Self>();
+    /// Schreibt den Genomspeicher, sofern die Sicherheitsregeln es erlauben
+    pub fn save_genome_store(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
+        let action = PolicyAction::WriteFile {
+            path: &self.genome_store.index_path(),
+            bytes: self.genome_store.save_size()?,
+            disk_usage: self.disk_usage,
+        };
+        if self.safety_interlocks.check(&action, self.generation).is_err() {
+            return Ok(false);
+        }
+        
+        self.genome_store.save()?;
+        self.storage.invalidate(&self.genome_store.index_path());
+        Ok(true)
+    }
+    
+    /// Alle Wechsel des aktiven Genoms, älteste zuerst
+    pub fn generation_history(&self) -> &[HistoryEvent] {
+        &self.genome_store.history
+    }
+    
+    /// Inhalt des in `generation` aktiven Genoms
+    pub fn genome_at(&self, generation: u64) -> Result<(GenomeId, String), Box<dyn std::error::Error>> {
+        let event = self.genome_store.active_at(generation)
+            .ok_or_else(|| format!("Für Generation {} ist kein Genom verzeichnet", generation))?;
+        let genome = self.genome_store.get(&event.genome)
+            .ok_or_else(|| format!("Genom {} fehlt im Genomspeicher", event.genome))?;
+        Ok((event.genome.clone(), genome))
+    }
+    
+    /// Zeilenweiser Unterschied der in `from` und `to` aktiven Genome
+    pub fn diff_generations(&self, from: u64, to: u64) -> Result<String, Box<dyn std::error::Error>> {
+        let (_, old) = self.genome_at(from)?;
+        let (_, new) = self.genome_at(to)?;
+        Ok(line_diff(&old, &new))
+    }
+    
+    /// Macht das in `generation` aktive Genom wieder zum `primary_genome` und setzt die
+    /// Population darauf zurück. Mit `write_source` wird es zusätzlich in die
+    /// Quelldatei des Kerns (`paths.source`) geschrieben. Die Rückkehr wird als Ereignis verbucht.
+    pub fn rollback_to(&mut self, generation: u64, write_source: bool) -> Result<GenomeId, Box<dyn std::error::Error>> {
+        let (id, genome) = self.genome_at(generation)?;
+        let record = self.genome_store.record(&id).cloned();
         
-        // CPU-Nutzung messen (vereinfacht)
-        let start = Instant::now();
-        let mut counter = 0;
-        while start.elapsed() < Duration::from_millis(100) {
-            counter += 1;
+        // Auch eine Rückkehr ist eine Übernahme und unterliegt den Sicherheitsregeln
+        let backed_up = self.genome_store.is_persisted(&genome_id(&self.primary_genome));
+        let compiles = record.as_ref().and_then(|r| r.compiled) != Some(false);
+        let action = PolicyAction::AdoptGenome { genome: &genome, compiles, backed_up };
+        self.safety_interlocks.check(&action, self.generation)?;
+        
+        let fitness = record.as_ref().and_then(|r| r.fitness);
+        self.primary_genome = genome;
+        self.fitness_score = fitness.unwrap_or(0.0);
+        self.incumbent_evaluated = fitness.is_some();
+        self.population = Population::seed(&self.primary_genome, self.population.config.clone());
+        for individual in self.population.individuals.iter_mut() {
+            individual.fitness = self.fitness_score;
+            individual.origin = "Rollback".to_string();
         }
-        self.cpu_usage = counter as f64 / 1_000_000.0;
+        
+        // Ausdrücklicher Auftrag des Bedieners - daher nicht über `write_guarded`,
+        // dessen Regeln `src` für die Evolution sperren
+        if write_source {
+            let source = self.config.paths.source_path();
+            fs::write(&source, &self.primary_genome)?;
+            println!("📝 Genom nach {} geschrieben", source.display());
+        }
+        
+        self.genome_store.record_event(self.generation, HistoryEventKind::Rollback, &id, self.fitness_score);
+        self.save_genome_store()?;
+        self.save_checkpoint()?;
+        
+        println!("⏪ Zurück zu Generation {} (Genom {})", generation, &id[..12]);
+        Ok(id)
+    }
+    
+    /// Analysiert die Ausführungsumgebung und Systemressourcen
+    pub fn analyze_environment(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        // Prozess- und Hostmetriken messen
+        let sample = self.system_monitor.sample();
+        self.memory_usage = sample.rss_bytes as usize;
+        self.cpu_usage = sample.cpu_percent;
         
         // Festplattennutzung berechnen
-        self.disk_usage = self.calculate_disk_usage()?;
+        let usage = self.storage_usage()?;
+        self.disk_usage = usage.total_bytes();
         
         // Aktuelle Metriken ausgeben
-        println!("📊 Umgebungsanalyse: Speicher={}KB, CPU={:.2}, Disk={}MB", 
-                 self.memory_usage / 1024, 
+        println!("📊 Umgebungsanalyse: RSS={}MB, CPU={:.1}%, Load={:.2}, Disk={}MB, frei={}GB, FDs={}", 
+                 self.memory_usage / (1024 * 1024), 
                  self.cpu_usage,
-                 self.disk_usage / (1024 * 1024));
+                 sample.load_average[0],
+                 self.disk_usage / (1024 * 1024),
+                 sample.free_disk_bytes / (1024 * 1024 * 1024),
+                 sample.open_file_descriptors.map(|fds| fds.to_string()).unwrap_or_else(|| "-".to_string()));
+        println!("💽 Speicherbelegung: {}", usage);
         
         Ok(())
     }
     
-    /// Berechnet die aktuelle Festplattennutzung
-    pub fn calculate_disk_usage(&self) -> Result<u64, Box<dyn std::error::Error>> {
-        let mut total_size = 0u64;
+    /// Berechnet die aktuelle Festplattennutzung aller Daten der Instanz
+    pub fn calculate_disk_usage(&mut self) -> Result<u64, Box<dyn std::error::Error>> {
+        Ok(self.storage_usage()?.total_bytes())
+    }
+    
+    /// Belegung je Kategorie; nur seit dem letzten Durchlauf veränderte Verzeichnisse werden gelesen
+    pub fn storage_usage(&mut self) -> Result<StorageUsage, Box<dyn std::error::Error>> {
+        // Logs und Zustandsdateien werden fortgeschrieben, ohne dass sich ihr Verzeichnis ändert
+        self.storage.invalidate(&self.config.paths.log_path());
+        self.storage.invalidate(&self.state_dir);
+        self.storage.scan()
+    }
+    
+    /// Lernt aus Internet-Ressourcen. Mit `learning.offline` ausschließlich aus dem
+    /// Cache, ohne einen einzigen Netzwerkzugriff.
+    pub async fn learn_from_internet(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let offline = self.config.learning.offline;
+        if offline {
+            println!("📴 Lerne offline aus dem Cache...");
+        } else {
+            println!("🌐 Suche nach Wissen im Internet...");
+        }
         
-        // Größe des Wissensverzeichnisses berechnen
-        if self.knowledge_dir.exists() {
-            for entry in fs::read_dir(&self.knowledge_dir)? {
-                let entry = entry?;
-                let metadata = entry.metadata()?;
-                if metadata.is_file() {
-                    total_size += metadata.len();
-                }
+        // Ist die Frontier abgearbeitet, beginnt eine neue Runde ab den Startseiten (`learning.urls`);
+        // offline zusätzlich ab allen Seiten des Caches, damit ein vorab befüllter Bestand genutzt wird
+        let crawl = self.config.learning.crawl.clone();
+        if self.frontier.queue.is_empty() {
+            let mut seeds = self.config.learning.urls.clone();
+            if offline {
+                seeds.extend(self.cache_store.entries.values().filter_map(|entry| entry.url.clone()));
+            }
+            if seeds.is_empty() {
+                println!("ℹ️ Keine Lernquellen konfiguriert");
+                return Ok(());
             }
+            let seeded = self.frontier.begin_round(&seeds);
+            println!("🔁 Crawl-Runde {} beginnt mit {} Startseiten", self.frontier.rounds, seeded);
         }
         
-        // Größe des Cache-Verzeichnisses berechnen
-        if self.internet_cache.exists() {
-            for entry in fs::read_dir(&self.internet_cache)? {
-                let entry = entry?;
-                let metadata = entry.metadata()?;
-                if metadata.is_file() {
-                    total_size += metadata.len();
+        // Nächste URL, deren Host wieder abgerufen werden darf und die robots.txt erlaubt
+        let entry = loop {
+            let Some(entry) = self.frontier.next_ready(chrono::Utc::now().timestamp_millis(), &crawl) else {
+                if self.frontier.queue.is_empty() {
+                    println!("✅ Crawl-Runde {} abgeschlossen ({} URLs besucht)", self.frontier.rounds, self.frontier.visited.len());
+                } else {
+                    println!("⏳ Kein Host bereit ({} URLs warten auf ihren Crawl-Delay)", self.frontier.queue.len());
+                }
+                return self.save_frontier();
+            };
+            
+            // Offline gibt es keinen Netzwerkzugriff, nur die bereits bekannte robots.txt gilt
+            if offline {
+                if crawl.respect_robots && !self.frontier.robots_allow(&entry.url) {
+                    self.frontier.mark_visited(&entry.url);
+                    continue;
+                }
+                break entry;
+            }
+            
+            // Sicherheitsregeln vor dem Netzwerkzugriff prüfen; ist die robots.txt des Hosts
+            // unbekannt oder abgelaufen, gilt der Zugriff zuerst ihr
+            let needs_robots = crawl.respect_robots && self.frontier.needs_robots(&entry.url, chrono::Utc::now().timestamp());
+            let target = if needs_robots { robots_url(&entry.url) } else { entry.url.clone() };
+            let action = PolicyAction::NetworkAccess {
+                url: &target,
+                since_last_access: self.last_internet_access.elapsed(),
+            };
+            if let Err(violation) = self.safety_interlocks.check(&action, self.generation) {
+                // Nur der Anfrageabstand vergeht; Schema und Domain bleiben verboten
+                if violation.rule == "controlled_resource_usage" {
+                    self.frontier.requeue(entry);
+                    return self.save_frontier();
+                }
+                self.frontier.mark_visited(&entry.url);
+                continue;
+            }
+            
+            // Die Seite wartet danach erneut, bis ihr Host wieder abgerufen werden darf
+            if needs_robots {
+                self.fetch_robots(&entry.url).await;
+                self.frontier.requeue(entry);
+                continue;
+            }
+            if crawl.respect_robots && !self.frontier.robots_allow(&entry.url) {
+                println!("🤖 robots.txt untersagt {}", entry.url);
+                self.frontier.mark_visited(&entry.url);
+                continue;
+            }
+            break entry;
+        };
+        let selected_url = entry.url.as_str();
+        let cached = self.cache_store.entries.get(&Self::cache_name(selected_url)).cloned();
+        let fresh = cached.as_ref()
+            .filter(|cached| cached.http.as_ref().is_some_and(|http| http.is_fresh(chrono::Utc::now().timestamp())));
+        
+        // Inhalt und Content-Type aus dem Cache oder über den konfigurierten Transport
+        let page = if offline {
+            self.frontier.mark_visited(selected_url);
+            match &cached {
+                Some(cached) => Some(self.cached_page(cached)?),
+                None => {
+                    println!("📴 {} ist nicht im Cache", selected_url);
+                    None
+                },
+            }
+        } else if let Some(fresh) = fresh {
+            println!("🗄️ Noch frisch im Cache: {}", selected_url);
+            self.frontier.mark_visited(selected_url);
+            Some(self.cached_page(fresh)?)
+        } else {
+            let page = self.fetch_page(selected_url, cached.as_ref()).await?;
+            
+            // Aktualisiere Zeitstempel des letzten Zugriffs
+            self.last_internet_access = Instant::now();
+            page
+        };
+        
+        if let Some((content, content_type)) = page {
+            // Verarbeite und extrahiere Wissen (stark vereinfacht)
+            self.extract_knowledge_from_content(&content, Some(selected_url))?;
+            
+            // Links der Seite erweitern die Frontier
+            if content_type.as_deref().is_none_or(|content_type| content_type.contains("html")) {
+                let links = extract_links(&content, selected_url);
+                let added = self.frontier.discover(&entry, &links, &crawl, &self.config.learning.allowed_domains);
+                if added > 0 {
+                    println!("🔗 {} neue Links in die Frontier aufgenommen ({} wartend)", added, self.frontier.queue.len());
                 }
             }
         }
         
-        Ok(total_size)
+        self.save_frontier()
     }
     
-    /// Lernt aus Internet-Ressourcen
-    pub async fn learn_from_internet(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        println!("🌐 Suche nach Wissen im Internet...");
-        
-        // Liste von URLs, die für das Lernen interessant sein könnten
-        // In einer echten Implementation würde dies dynamisch ermittelt
-        let learning_urls = vec![
-            "https://doc.rust-lang.org/book/",
-            "https://en.wikipedia.org/wiki/Genetic_algorithm",
-            "https://en.wikipedia.org/wiki/Self-modifying_code",
-        ];
+    /// Name des Cache-Eintrags einer URL; ein erneuter Abruf ersetzt den Eintrag
+    fn cache_name(url: &str) -> String {
+        format!("evoli_cache_{}.html", &content_hash(url.as_bytes())[..16])
+    }
+    
+    /// Ruft `url` ab, bedingt, falls eine Kopie im Cache liegt. Ist der Server nicht
+    /// erreichbar oder gestört, wird die veraltete Kopie verwendet, sofern sie nicht
+    /// `must-revalidate` verlangt. Liefert Inhalt und Content-Type.
+    async fn fetch_page(&mut self, url: &str, cached: Option<&ContentEntry>) -> Result<Option<Page>, Box<dyn std::error::Error>> {
+        let conditional = cached
+            .and_then(|cached| cached.http.as_ref())
+            .map(HttpCacheInfo::conditional_headers)
+            .unwrap_or_default();
         
-        // Wähle zufällig eine URL aus
-        let mut rng = thread_rng();
-        let selected_url = learning_urls[rng.gen_range(0..learning_urls.len())];
+        // Stelle HTTP-Anfrage über den konfigurierten Transport
+        let revalidation = if conditional.is_empty() { "" } else { ", Revalidierung" };
+        println!("📡 Lerne von: {} ({}{})", url, self.transport.name(), revalidation);
+        self.live_metrics.internet_requests += 1;
+        let fetched = self.transport.get(url, &conditional).await;
+        self.frontier.record_fetch(url, chrono::Utc::now().timestamp_millis());
+        let now = chrono::Utc::now().timestamp();
         
-        // Erstelle HTTP-Client
-        let client = reqwest::Client::new();
+        let response = match fetched {
+            Ok(response) => response,
+            Err(e) => {
+                self.live_metrics.failed_internet_requests += 1;
+                println!("❌ Netzwerkfehler: {}", e);
+                return self.stale_page(cached);
+            },
+        };
         
-        // Stelle HTTP-Anfrage
-        println!("📡 Lerne von: {}", selected_url);
-        match client.get(selected_url).send().await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    // Lese Inhalt
-                    match response.text().await {
-                        Ok(content) => {
-                            // Speichere Inhalt im Cache
-                            let cache_filename = format!("evoli_cache_{}.html", 
-                                                        chrono::Local::now().format("%Y%m%d%H%M%S"));
-                            let cache_path = self.internet_cache.join(cache_filename);
-                            
-                            fs::write(&cache_path, &content)?;
-                            
-                            // Extrahiere relevante Informationen (vereinfacht)
-                            let content_length = content.len();
-                            println!("📥 Daten empfangen: {}KB", content_length / 1024);
-                            
-                            // Verarbeite und extrahiere Wissen (stark vereinfacht)
-                            self.extract_knowledge_from_content(&content)?;
-                        },
-                        Err(e) => println!("❌ Fehler beim Lesen des Inhalts: {}", e),
-                    }
-                } else {
-                    println!("❌ HTTP-Fehler: {}", response.status());
-                }
+        match cached {
+            Some(cached) if response.status == 304 => {
+                let http = cached.http.clone().unwrap_or_default().refreshed(&response.headers, now);
+                self.cache_store.set_http(&cached.name, http)?;
+                self.live_metrics.not_modified += 1;
+                println!("✅ Unverändert seit dem letzten Abruf (304)");
+                return Ok(Some(self.cached_page(cached)?));
             },
-            Err(e) => println!("❌ Netzwerkfehler: {}", e),
+            _ => {},
+        }
+        
+        if !response.is_success() {
+            self.live_metrics.failed_internet_requests += 1;
+            println!("❌ HTTP-Fehler: {}", response.status);
+            return if response.status >= 500 { self.stale_page(cached) } else { Ok(None) };
+        }
+        
+        let content_type = response.header("content-type").map(str::to_string);
+        let content = response.text();
+        self.live_metrics.bytes_downloaded += response.body.len() as u64;
+        println!("📥 Daten empfangen: {}KB", content.len() / 1024);
+        
+        // Speichere Inhalt samt Validatoren und Ablaufdatum im Cache
+        let cache_name = Self::cache_name(url);
+        if cacheable(&response.headers) {
+            let stored = self.store_guarded(StorageCategory::Cache, &cache_name, &response.body,
+                                            Some(url), content_type.as_deref())?;
+            if let Some(outcome) = stored {
+                if outcome.deduplicated {
+                    println!("♻️ Inhalt unverändert im Cache - kein neues Objekt gespeichert");
+                }
+                self.cache_store.set_http(&cache_name, HttpCacheInfo::from_headers(&response.headers, now))?;
+            }
+        } else if cached.is_some() {
+            self.cache_store.remove(&cache_name)?;
+            self.access_ledger.forget(&self.cache_store.entry_path(&cache_name));
+            self.storage.invalidate(&self.cache_store.index_path());
+            println!("🚫 Cache-Control: no-store - bisherige Kopie entfernt");
         }
         
-        // Aktualisiere Zeitstempel des letzten Zugriffs
+        Ok(Some((content, content_type)))
+    }
+    
+    /// Veraltete Kopie aus dem Cache, wenn der Server nicht antwortet
+    fn stale_page(&mut self, cached: Option<&ContentEntry>) -> Result<Option<Page>, Box<dyn std::error::Error>> {
+        match cached {
+            Some(cached) if !cached.http.as_ref().is_some_and(|http| http.must_revalidate) => {
+                println!("🗄️ Verwende die Kopie aus dem Cache vom {}", cached.fetched_at);
+                Ok(Some(self.cached_page(cached)?))
+            },
+            _ => Ok(None),
+        }
+    }
+    
+    /// Liest eine Seite aus dem Cache (Inhalt und Content-Type) und vermerkt den Zugriff
+    fn cached_page(&mut self, cached: &ContentEntry) -> Result<Page, Box<dyn std::error::Error>> {
+        let content = self.cache_store.get_string(&cached.name)?;
+        self.live_metrics.cache_hits += 1;
+        self.access_ledger.record_access(&self.cache_store.entry_path(&cached.name), chrono::Utc::now().timestamp());
+        Ok((content, cached.content_type.clone()))
+    }
+    
+    /// Liest die robots.txt zum Host von `url` neu. Der Abruf zählt wie ein Seitenabruf
+    /// und hält Anfrageabstand und Crawl-Delay ein. Fehlt sie (4xx), ist alles erlaubt;
+    /// ist sie nicht erreichbar, bis zum nächsten Versuch nichts.
+    async fn fetch_robots(&mut self, url: &str) {
+        let robots_url = robots_url(url);
+        println!("🤖 Lese {} ({})", robots_url, self.transport.name());
+        self.live_metrics.internet_requests += 1;
+        let fetched = self.transport.get(&robots_url, &BTreeMap::new()).await;
+        self.frontier.record_request(&robots_url, chrono::Utc::now().timestamp_millis());
         self.last_internet_access = Instant::now();
         
+        let (robots, valid_for) = match fetched {
+            Ok(response) if response.is_success() => {
+                self.live_metrics.bytes_downloaded += response.body.len() as u64;
+                (RobotsRules::parse(&response.text(), CRAWLER_AGENT), ROBOTS_TTL_SECS)
+            },
+            Ok(response) if (400..500).contains(&response.status) => (RobotsRules::allow_all(), ROBOTS_TTL_SECS),
+            Ok(response) => {
+                self.live_metrics.failed_internet_requests += 1;
+                println!("⚠️ {} nicht lesbar (HTTP {})", robots_url, response.status);
+                (RobotsRules::disallow_all(), ROBOTS_RETRY_SECS)
+            },
+            Err(e) => {
+                self.live_metrics.failed_internet_requests += 1;
+                println!("⚠️ {} nicht erreichbar: {}", robots_url, e);
+                (RobotsRules::disallow_all(), ROBOTS_RETRY_SECS)
+            },
+        };
+        self.frontier.set_robots(url, robots, chrono::Utc::now().timestamp() + valid_for);
+    }
+    
+    /// Sichert die Crawl-Frontier im Zustandsverzeichnis
+    pub fn save_frontier(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let frontier = serde_json::to_vec(&self.frontier)?;
+        self.write_guarded(&self.state_dir.join("crawl_frontier.json"), &frontier)?;
         Ok(())
     }
     
-    /// Extrahiert Wissen aus heruntergeladenen Inhalten
-    pub fn extract_knowledge_from_content(&self, content: &str) -> Result<(), Box<dyn std::error::Error>> {
+    /// Extrahiert Wissen aus heruntergeladenen Inhalten von `source`
+    pub fn extract_knowledge_from_content(&mut self, content: &str, source: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
         // In einer echten Implementation würde hier eine komplexe 
         // Textanalyse und Informationsextraktion stattfinden
         
@@ -380,14 +1023,21 @@ impl EnhancedEvoliKern {
         if !extracted_code.is_empty() {
             let knowledge_filename = format!("evoli_knowledge_{}.rs", 
                                            chrono::Local::now().format("%Y%m%d%H%M%S"));
-            let knowledge_path = self.knowledge_dir.join(knowledge_filename);
             
-            let mut file = File::create(knowledge_path)?;
+            let mut file = Vec::new();
             for (i, code) in extracted_code.iter().enumerate() {
                 writeln!(file, "// Extrahiertes Code-Beispiel {}\n{}\n", i + 1, code)?;
             }
             
-            println!("💡 {} Code-Beispiele extrahiert und gespeichert", extracted_code.len());
+            // Bereits bekannte Beispiele (z.B. von derselben Seite) nicht erneut ablegen
+            if let Some(known) = self.knowledge_store.find_by_hash(&content_hash(&file)) {
+                let known_path = self.knowledge_store.entry_path(&known.name);
+                println!("♻️ Code-Beispiele bereits bekannt ({})", known.name);
+                self.access_ledger.record_access(&known_path, chrono::Utc::now().timestamp());
+            } else if self.store_guarded(StorageCategory::Knowledge, &knowledge_filename, &file, source, Some("text/x-rust"))?.is_some() {
+                self.live_metrics.knowledge_items += extracted_code.len() as u64;
+                println!("💡 {} Code-Beispiele extrahiert und gespeichert", extracted_code.len());
+            }
         } else {
             println!("ℹ️ Keine relevanten Code-Beispiele gefunden");
         }
@@ -395,72 +1045,410 @@ impl EnhancedEvoliKern {
         Ok(())
     }
     
-    /// Führt die eigentliche Evolution durch
+    /// Führt die eigentliche Evolution durch: erzeugt Nachkommen aus der Population,
+    /// bewertet sie und übernimmt das beste Individuum als `primary_genome`
     pub fn evolve(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        println!("🧬 Starte Evolutionsprozess...");
+        println!("🧬 Starte Evolutionsprozess (Population: {} Individuen)...", self.population.len());
         
-        // 1. Wähle Mutationsstrategie
-        let mut rng = thread_rng();
-        let strategy_index = rng.gen_range(0..self.mutation_strategies.len());
-        let strategy = &self.mutation_strategies[strategy_index];
-        
-        println!("🔄 Verwende Mutationsstrategie: {}", strategy.name());
+        // 0. Amtierendes Genom in jeder Generation neu bewerten, damit sich die Kandidaten an
+        // einer Messung unter gleichen Bedingungen messen (Kompilier- und Laufzeiten schwanken).
+        // Der erste Build baut alle Abhängigkeiten und dient nur zum Aufwärmen.
+        if !self.build_cache_warm {
+            println!("🔥 Wärme den Build-Cache auf...");
+            self.measure_candidate(&self.primary_genome)?;
+            self.build_cache_warm = true;
+        }
+        let (metrics, diagnostics) = self.measure_candidate(&self.primary_genome)?;
+        self.record_build("Amtierend", &metrics, diagnostics);
+        self.fitness_score = self.evaluate_candidate(&self.primary_genome, &metrics);
+        let objectives = evaluate_objectives(&self.objective_evaluators, &self.primary_genome, &metrics);
+        for individual in self.population.individuals.iter_mut() {
+            if individual.genome == self.primary_genome {
+                individual.fitness = self.fitness_score;
+                individual.objectives = objectives.clone();
+            }
+        }
+        self.incumbent_evaluated = true;
+        let id = self.genome_store.put(&self.primary_genome, &[], "Seed", self.generation);
+        self.genome_store.set_outcome(&id, self.fitness_score, metrics.compile_success);
+        println!("📏 Amtierendes Genom bewertet: Fitness {:.3}", self.fitness_score);
         
-        // 2. Wende Mutation an
-        let mutated_genome = strategy.mutate(&self.primary_genome);
+        let mut offspring = Vec::new();
         
-        // 3. Validiere neues Genom (Kompilierbarkeit)
-        if mutated_genome != self.primary_genome {
-            fs::write("evoli_next_gen.rs", &mutated_genome)?;
+        for _ in 0..self.population.offspring_count() {
+            // 1. Wähle Elternteil und Mutationsstrategie
+            let parent = self.population.select(&mut self.rng).clone();
+            let strategy = &self.mutation_strategies[self.rng.gen_range(0..self.mutation_strategies.len())];
+            let mut origin = strategy.name();
+            let mut parent_ids = vec![genome_id(&parent.genome)];
             
-            let compile_result = Command::new("rustc")
-                .arg("evoli_next_gen.rs")
-                .arg("--out-dir")
-                .arg("./evolved")
-                .output();
+            // 2. Rekombiniere ggf. mit einem zweiten Elternteil
+            let mut child_genome = parent.genome.clone();
+            if !self.crossover_strategies.is_empty() && self.rng.gen::<f64>() < self.population.config.crossover_rate {
+                let partner = self.population.select(&mut self.rng);
+                let crossover = &self.crossover_strategies[self.rng.gen_range(0..self.crossover_strategies.len())];
                 
-            match compile_result {
-                Ok(output) => {
-                    if output.status.success() {
-                        // Kompilierung erfolgreich, übernehme neues Genom
-                        println!("✅ Evolution erfolgreich - neues Genom kompilierbar");
-                        
-                        // Integriere eventuell Wissen aus früheren Downloads
-                        let enhanced_genome = self.integrate_knowledge_into_code(&mutated_genome)?;
-                        self.primary_genome = enhanced_genome;
-                        
-                        // Versuche, neue Module zu erstellen
-                        self.try_create_new_module()?;
-                    } else {
-                        println!("❌ Evolution fehlgeschlagen - Kompilierungsfehler");
-                        println!("📄 Fehlerdetails: {}", String::from_utf8_lossy(&output.stderr));
-                    }
-                },
-                Err(e) => println!("❌ Kompilierungsprozess fehlgeschlagen: {}", e)
+                if partner.genome != parent.genome {
+                    println!("🔀 Verwende Crossover-Strategie: {}", crossover.name());
+                    child_genome = crossover.crossover(&parent.genome, &partner.genome, &mut self.rng);
+                    origin = format!("{}+{}", crossover.name(), origin);
+                    parent_ids.push(genome_id(&partner.genome));
+                }
             }
+            
+            println!("🔄 Verwende Mutationsstrategie: {}", strategy.name());
+            
+            // 3. Wende Mutation an
+            let mutated_genome = strategy.mutate(&child_genome, &mut self.rng);
+            
+            // 4. Validiere neues Genom (Kompilierbarkeit) und bewerte es
+            let (mutated_genome, fitness, objectives) = if mutated_genome == parent.genome {
+                println!("ℹ️ Keine Änderungen durch Mutation");
+                (mutated_genome, parent.fitness, parent.objectives.clone())
+            } else {
+                // Kandidaten mit neuen Fähigkeiten werden weder gebaut noch ausgeführt
+                let action = PolicyAction::EvaluateCandidate { genome: &mutated_genome };
+                if self.safety_interlocks.check(&action, self.generation).is_err() {
+                    self.quarantine(&mutated_genome, &origin)?;
+                    continue;
+                }
+                let (genome, metrics) = self.build_with_repair(mutated_genome, &mut origin)?;
+                let id = self.genome_store.put(&genome, &parent_ids, &origin, self.generation + 1);
+                if !metrics.compile_success {
+                    self.genome_store.set_outcome(&id, 0.0, false);
+                    self.record_strategy_outcome(&origin, false, false);
+                    continue;
+                }
+                let fitness = self.evaluate_candidate(&genome, &metrics);
+                self.genome_store.set_outcome(&id, fitness, true);
+                self.record_strategy_outcome(&origin, true, fitness > parent.fitness);
+                let objectives = evaluate_objectives(&self.objective_evaluators, &genome, &metrics);
+                (genome, fitness, objectives)
+            };
+            
+            offspring.push(Individual {
+                genome: mutated_genome,
+                fitness,
+                objectives,
+                generation: self.generation + 1,
+                origin,
+            });
+        }
+        
+        // 5. Pareto-Archiv aktualisieren und Nachkommen in die Population übernehmen
+        let mut archive_changed = false;
+        for child in &offspring {
+            archive_changed |= self.pareto_archive.insert(ArchiveEntry {
+                genome: child.genome.clone(),
+                objectives: child.objectives.clone(),
+                fitness: child.fitness,
+                generation: child.generation,
+                origin: child.origin.clone(),
+            });
+        }
+        let archive_action = PolicyAction::WriteFile {
+            path: self.pareto_archive.path(),
+            bytes: self.pareto_archive.serialized_size()?,
+            disk_usage: self.disk_usage,
+        };
+        if archive_changed && self.safety_interlocks.check(&archive_action, self.generation).is_ok() {
+            self.pareto_archive.save()?;
+            println!("🏅 Pareto-Front aktualisiert: {} nicht-dominierte Genome", self.pareto_archive.entries.len());
+        }
+        
+        self.population.replace(offspring);
+        
+        // 6. Bestes Individuum wird nur bei echter Verbesserung zum primären Genom
+        let improved = self.population.best()
+            .map(|best| best.genome != self.primary_genome && best.fitness > self.fitness_score)
+            .unwrap_or(false);
+        
+        if improved {
+            let best_genome = self.population.best().map(|best| best.genome.clone()).unwrap_or_default();
+            
+            // Integriere eventuell Wissen aus früheren Downloads
+            let mut enhanced_genome = self.integrate_knowledge_into_code(&best_genome)?;
+            
+            // Auch das erweiterte Genom darf keine neuen Fähigkeiten einschleusen
+            if enhanced_genome != best_genome {
+                let action = PolicyAction::EvaluateCandidate { genome: &enhanced_genome };
+                if self.safety_interlocks.check(&action, self.generation).is_err() {
+                    self.quarantine(&enhanced_genome, "KnowledgeIntegration")?;
+                    enhanced_genome = best_genome.clone();
+                }
+            }
+            
+            // Ein durch Wissen erweitertes Genom wird wie jeder Nachkomme gebaut und bewertet;
+            // es ersetzt das beste Individuum nur, wenn es kompiliert und selbst das amtierende übertrifft
+            let best_fitness = self.population.best().map(|best| best.fitness).unwrap_or(self.fitness_score);
+            let (adopted_genome, adopted_fitness, adopted_objectives) = if enhanced_genome != best_genome {
+                let mut origin = "KnowledgeIntegration".to_string();
+                let (genome, metrics) = self.build_with_repair(enhanced_genome, &mut origin)?;
+                let id = self.genome_store.put(&genome, &[genome_id(&best_genome)], &origin, self.generation + 1);
+                let fitness = if metrics.compile_success { self.evaluate_candidate(&genome, &metrics) } else { 0.0 };
+                self.genome_store.set_outcome(&id, fitness, metrics.compile_success);
+                if !metrics.compile_success {
+                    println!("⚠️ Wissensintegration nicht kompilierbar - übernehme bestes Individuum");
+                    (best_genome.clone(), best_fitness, None)
+                } else if fitness > self.fitness_score {
+                    let objectives = evaluate_objectives(&self.objective_evaluators, &genome, &metrics);
+                    (genome, fitness, Some(objectives))
+                } else {
+                    println!("ℹ️ Wissensintegration ohne Verbesserung (Fitness {:.3}) - übernehme bestes Individuum", fitness);
+                    (best_genome.clone(), best_fitness, None)
+                }
+            } else {
+                (best_genome.clone(), best_fitness, None)
+            };
+            
+            // Beide Wege liefern nur kompilierte Genome
+            let backed_up = self.genome_store.is_persisted(&genome_id(&self.primary_genome));
+            let action = PolicyAction::AdoptGenome { genome: &adopted_genome, compiles: true, backed_up };
+            if self.safety_interlocks.check(&action, self.generation).is_err() {
+                println!("⛔ Übernahme des neuen Genoms blockiert");
+                return Ok(());
+            }
+            
+            if let (Some(objectives), Some(best)) = (adopted_objectives, self.population.best_mut()) {
+                best.genome = adopted_genome.clone();
+                best.fitness = adopted_fitness;
+                best.objectives = objectives;
+            }
+            self.primary_genome = adopted_genome;
+            
+            let previous_fitness = self.fitness_score;
+            self.fitness_score = adopted_fitness;
+            let id = genome_id(&self.primary_genome);
+            self.genome_store.record_event(self.generation + 1, HistoryEventKind::Adopted, &id, self.fitness_score);
+            self.live_metrics.adopted_genomes += 1;
+            println!("✅ Neues bestes Genom übernommen (Fitness {:.3} → {:.3})", previous_fitness, self.fitness_score);
+            
+            // Versuche, neue Module zu erstellen
+            self.try_create_new_module()?;
         } else {
-            println!("ℹ️ Keine Änderungen durch Mutation");
+            println!("ℹ️ Kein Kandidat besser als das amtierende Genom (Fitness {:.3})", self.fitness_score);
+        }
+        
+        Ok(())
+    }
+    
+    /// Baut einen Kandidaten und verbucht seine Diagnosen. Scheitert der Build,
+    /// werden bis zu `MAX_REPAIR_ROUNDS` Mal die Compiler-Vorschläge angewendet.
+    fn build_with_repair(&mut self, mut genome: String, origin: &mut String) -> Result<(String, RuntimeMetrics), Box<dyn std::error::Error>> {
+        const MAX_REPAIR_ROUNDS: usize = 2;
+        
+        let (mut metrics, mut diagnostics) = self.measure_candidate(&genome)?;
+        self.record_build(origin, &metrics, diagnostics.clone());
+        
+        for round in 1..=MAX_REPAIR_ROUNDS {
+            if metrics.compile_success {
+                break;
+            }
+            let Some(repaired) = RepairMutationStrategy::repair(&genome, &diagnostics) else {
+                break;
+            };
+            
+            println!("🩹 Wende Compiler-Vorschläge an (Versuch {}/{})", round, MAX_REPAIR_ROUNDS);
+            genome = repaired;
+            origin.push_str("+RepairMutation");
+            (metrics, diagnostics) = self.measure_candidate(&genome)?;
+            self.record_build(origin, &metrics, diagnostics.clone());
+            
+            if metrics.compile_success {
+                self.diagnostic_stats.repaired_builds += 1;
+                println!("✅ Kandidat durch Compiler-Vorschläge repariert");
+            }
         }
         
+        Ok((genome, metrics))
+    }
+    
+    /// Legt ein abgelehntes Genom zur späteren Begutachtung im Quarantäneverzeichnis ab
+    fn quarantine(&mut self, genome: &str, origin: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path = self.state_dir.join("quarantine").join(format!("gen_{}_{}.rs", self.generation + 1, &genome_id(genome)[..16]));
+        let contents = format!("// Quarantäne: erzeugt durch {}\n{}", origin, genome);
+        if self.write_guarded(&path, contents.as_bytes())? {
+            println!("☣️ Kandidat in Quarantäne verschoben: {}", path.display());
+        }
         Ok(())
     }
     
+    /// Nimmt die Diagnosen eines Builds in die Fehlerstatistik auf
+    fn record_build(&mut self, origin: &str, metrics: &RuntimeMetrics, diagnostics: Vec<Diagnostic>) {
+        if metrics.compile_success {
+            self.live_metrics.successful_compilations += 1;
+        } else {
+            self.live_metrics.failed_compilations += 1;
+        }
+        self.diagnostic_stats.record(CandidateReport {
+            generation: self.generation + 1,
+            origin: origin.to_string(),
+            success: metrics.compile_success,
+            diagnostics,
+        });
+    }
+    
+    /// Zählt das Ergebnis eines Kandidaten für jeden beteiligten Operator
+    /// (`origin` wie "ItemCrossover+LiteralMutation+RepairMutation")
+    fn record_strategy_outcome(&mut self, origin: &str, compiled: bool, improved: bool) {
+        for operator in origin.split('+') {
+            let outcome = self.live_metrics.strategy_outcomes.entry(operator.to_string()).or_default();
+            outcome.attempts += 1;
+            outcome.compiled += compiled as u64;
+            outcome.improved += improved as u64;
+        }
+    }
+    
+    /// Baut ein Kandidaten-Genom isoliert und misst Warnungen, Größe,
+    /// Kompilierzeit sowie Testergebnisse. Liefert zusätzlich die Compiler-Diagnosen.
+    pub fn measure_candidate(&self, genome: &str) -> Result<(RuntimeMetrics, Vec<Diagnostic>), Box<dyn std::error::Error>> {
+        let mut metrics = self.runtime_metrics();
+        
+        let build = self.build_sandbox.build(genome)?;
+        metrics.compile_success = build.success;
+        metrics.compile_time = build.duration;
+        metrics.warning_count = build.warning_count;
+        metrics.binary_size = build.artifact_size;
+        
+        if build.success {
+            println!("✅ Kandidat kompilierbar ({} Warnungen, {:.1}s)", 
+                     build.warning_count, build.duration.as_secs_f64());
+        } else {
+            println!("❌ Kandidat verworfen - Kompilierungsfehler");
+            println!("📄 Fehlerdetails: {}", build.error_report());
+        }
+        
+        // Die Laufzeit der Tests dient als Benchmark
+        if let Some(tests) = build.tests {
+            metrics.tests_passed = tests.passed;
+            metrics.tests_failed = tests.failed;
+            metrics.benchmark_runtime = Some(tests.duration);
+            
+            let execution = &tests.execution;
+            if execution.timed_out {
+                println!("⏱️ Testlauf nach {:.1}s abgebrochen (Zeitlimit)", execution.wall_time.as_secs_f64());
+            } else if !execution.success() {
+                println!("⚠️ Testlauf endete mit Status {:?} (Signal {:?})", execution.exit_code, execution.signal);
+            }
+            metrics.execution = Some(tests.execution);
+        }
+        
+        Ok((metrics, build.diagnostics))
+    }
+    
+    /// Schreibt `contents` nach `path`, sofern die Sicherheitsregeln es erlauben.
+    /// Liefert `false`, wenn der Schreibzugriff blockiert wurde.
+    pub fn write_guarded(&mut self, path: &Path, contents: &[u8]) -> Result<bool, Box<dyn std::error::Error>> {
+        let action = PolicyAction::WriteFile {
+            path,
+            bytes: contents.len() as u64,
+            disk_usage: self.disk_usage,
+        };
+        if self.safety_interlocks.check(&action, self.generation).is_err() {
+            return Ok(false);
+        }
+        
+        fs::write(path, contents)?;
+        self.storage.invalidate(path);
+        Ok(true)
+    }
+
+    /// Legt `payload` komprimiert im Cache- bzw. Wissensspeicher ab, sofern die
+    /// Sicherheitsregeln es erlauben. Liefert `None`, wenn der Schreibzugriff blockiert wurde.
+    pub fn store_guarded(
+        &mut self,
+        category: StorageCategory,
+        name: &str,
+        payload: &[u8],
+        url: Option<&str>,
+        content_type: Option<&str>,
+    ) -> Result<Option<PutOutcome>, Box<dyn std::error::Error>> {
+        let store = match category {
+            StorageCategory::Knowledge => &mut self.knowledge_store,
+            _ => &mut self.cache_store,
+        };
+        let path = store.entry_path(name);
+        let action = PolicyAction::WriteFile {
+            path: &path,
+            bytes: payload.len() as u64,
+            disk_usage: self.disk_usage,
+        };
+        if self.safety_interlocks.check(&action, self.generation).is_err() {
+            return Ok(None);
+        }
+
+        let outcome = store.put(name, payload, url, content_type)?;
+        self.storage.invalidate(&store.index_path());
+        self.access_ledger.record_access(&path, chrono::Utc::now().timestamp());
+        Ok(Some(outcome))
+    }
+
+    /// Bewertet ein Genom anhand seiner Messwerte mit allen Fitness-Evaluatoren
+    pub fn evaluate_candidate(&self, genome: &str, metrics: &RuntimeMetrics) -> f64 {
+        // Ohne Evaluatoren ist Kompilierbarkeit das einzige Kriterium
+        if self.fitness_evaluators.is_empty() {
+            return if metrics.compile_success { 1.0 } else { 0.0 };
+        }
+        
+        aggregate_fitness(&self.fitness_evaluators, &self.fitness_weights, genome, metrics)
+    }
+    
+    /// Setzt das Gewicht eines Fitness-Evaluators für die gewichtete Aggregation
+    pub fn set_fitness_weight(&mut self, evaluator: &str, weight: f64) {
+        self.fitness_weights.insert(evaluator.to_string(), weight);
+    }
+    
+    /// Die fortlaufend gezählten Metriken des Kerns
+    pub fn metrics(&self) -> &RuntimeMetrics {
+        &self.live_metrics
+    }
+    
+    /// Momentaufnahme der Laufzeitmetriken: Zähler plus aktuelle System- und Ressourcenwerte
+    pub fn runtime_metrics(&self) -> RuntimeMetrics {
+        let latest = self.system_monitor.latest().cloned().unwrap_or_default();
+        RuntimeMetrics {
+            successful_compilations: self.live_metrics.successful_compilations,
+            failed_compilations: self.live_metrics.failed_compilations,
+            adopted_genomes: self.live_metrics.adopted_genomes,
+            internet_requests: self.live_metrics.internet_requests,
+            failed_internet_requests: self.live_metrics.failed_internet_requests,
+            bytes_downloaded: self.live_metrics.bytes_downloaded,
+            cache_hits: self.live_metrics.cache_hits,
+            not_modified: self.live_metrics.not_modified,
+            knowledge_items: self.live_metrics.knowledge_items,
+            storage_cleanups: self.live_metrics.storage_cleanups,
+            files_evicted: self.live_metrics.files_evicted,
+            bytes_evicted: self.live_metrics.bytes_evicted,
+            strategy_outcomes: self.live_metrics.strategy_outcomes.clone(),
+            memory_usage: self.memory_usage,
+            cpu_usage: self.cpu_usage,
+            disk_usage: self.disk_usage,
+            load_average: latest.load_average,
+            free_disk_bytes: latest.free_disk_bytes,
+            open_file_descriptors: latest.open_file_descriptors,
+            system_samples: self.system_monitor.samples(),
+            uptime: self.creation_time.elapsed(),
+            ..RuntimeMetrics::default()
+        }
+    }
+    
     /// Integriert Wissen aus gesammelten Daten in den Code
-    pub fn integrate_knowledge_into_code(&self, code: &str) -> Result<String, Box<dyn std::error::Error>> {
+    pub fn integrate_knowledge_into_code(&mut self, code: &str) -> Result<String, Box<dyn std::error::Error>> {
         let mut enhanced_code = code.to_string();
         
-        // Suche nach relevanten Wissensquellen
-        if self.knowledge_dir.exists() {
-            let mut rng = thread_rng();
-            let knowledge_files: Vec<_> = fs::read_dir(&self.knowledge_dir)?
-                .filter_map(|e| e.ok())
-                .filter(|e| e.path().extension().unwrap_or_default() == "rs")
+        // Suche nach relevanten Wissensquellen (nach Namen sortiert)
+        {
+            let knowledge_files: Vec<String> = self.knowledge_store.entries.keys()
+                .filter(|name| name.ends_with(".rs"))
+                .cloned()
                 .collect();
             
-            if !knowledge_files.is_empty() && rng.gen::<f64>() < 0.3 {
+            if !knowledge_files.is_empty() && self.rng.gen::<f64>() < self.config.evolution.knowledge_integration_rate {
                 // Wähle zufällig eine Wissensdatei
-                let knowledge_entry = &knowledge_files[rng.gen_range(0..knowledge_files.len())];
-                let knowledge_content = fs::read_to_string(knowledge_entry.path())?;
+                let knowledge_name = &knowledge_files[self.rng.gen_range(0..knowledge_files.len())];
+                let knowledge_content = self.knowledge_store.get_string(knowledge_name)?;
+                let knowledge_entry = &self.knowledge_store.entry_path(knowledge_name);
+                self.access_ledger.record_access(knowledge_entry, chrono::Utc::now().timestamp());
                 
                 // Extrahiere potenziell nützliche Funktionen (stark vereinfacht)
                 if let Some(func_start) = knowledge_content.find("fn ") {
@@ -470,6 +1458,7 @@ impl EnhancedEvoliKern {
                         // Füge als Hilfsfunktion hinzu
                         let insert_point = enhanced_code.rfind('}').unwrap_or(enhanced_code.len());
                         enhanced_code.insert_str(insert_point, &format!("\n// Von Internet gelernt\n{}\n", function));
+                        self.access_ledger.record_integration(knowledge_entry, chrono::Utc::now().timestamp());
                         
                         println!("🔄 Neue Funktion aus Wissensquelle integriert");
                     }
@@ -482,19 +1471,17 @@ impl EnhancedEvoliKern {
     
     /// Versucht, ein neues Modul zu erstellen
     pub fn try_create_new_module(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let mut rng = thread_rng();
-        
         // Mit geringer Wahrscheinlichkeit ein neues Modul erstellen
-        if rng.gen::<f64>() < 0.1 {
+        if self.rng.gen::<f64>() < self.config.evolution.new_module_rate {
             // Potenzielle Modultypen
-            let module_types = vec![
+            let module_types = [
                 "data_processor",
                 "network_interface",
                 "learning_system",
                 "code_analyzer",
             ];
             
-            let module_type = module_types[rng.gen_range(0..module_types.len())];
+            let module_type = module_types[self.rng.gen_range(0..module_types.len())];
             let module_name = format!("evoli_module_{}", module_type);
             
             // Prüfe, ob dieses Modul bereits existiert
@@ -523,10 +1510,10 @@ impl EnhancedEvoliKern {
                 
                 // Speichere in Moduldatenbank und als Datei
                 self.module_genomes.insert(module_name.clone(), module_code.clone());
-                let module_path = format!("{}.rs", module_name);
-                fs::write(&module_path, &module_code)?;
-                
-                println!("🧩 Neues Modul erstellt: {}", module_name);
+                let module_path = self.config.paths.evolved_path().join(format!("{}.rs", module_name));
+                if self.write_guarded(&module_path, module_code.as_bytes())? {
+                    println!("🧩 Neues Modul erstellt: {}", module_name);
+                }
             }
         }
         
@@ -535,10 +1522,9 @@ impl EnhancedEvoliKern {
     
     /// Entwickelt neue Evolutionsstrategien basierend auf gesammeltem Wissen
     pub fn develop_new_strategies(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let mut rng = thread_rng();
-        
         // Mit geringer Wahrscheinlichkeit neue Strategie entwickeln
-        if rng.gen::<f64>() < 0.05 && self.mutation_strategies.len() < 10 {
+        let evolution = &self.config.evolution;
+        if self.rng.gen::<f64>() < evolution.new_strategy_rate && self.mutation_strategies.len() < evolution.max_mutation_strategies {
             // Generiere einen neuen Strategienamen
             let strategy_name = format!("EvolvdStrategy_{}", self.generation);
             
@@ -549,6 +1535,7 @@ impl EnhancedEvoliKern {
             
             // Füge zur Liste der Strategien hinzu
             self.mutation_strategies.push(Box::new(new_strategy));
+            self.developed_strategies.push(strategy_name.clone());
             
             println!("🌱 Neue Mutationsstrategie entwickelt: {}", strategy_name);
         }
@@ -556,77 +1543,74 @@ impl EnhancedEvoliKern {
         Ok(())
     }
     
-    /// Verwaltet den Speicherplatz und begrenzt auf 1 TB
+    /// Verwaltet den Speicherplatz und begrenzt ihn auf `storage.max_bytes`:
+    /// löscht die Dateien mit dem geringsten Wert, siehe `eviction_plan`
     pub fn manage_storage(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Berechne aktuelle Nutzung
-        let current_usage = self.calculate_disk_usage()?;
+        let plan = self.eviction_plan()?;
         
-        // Wenn mehr als 80% des erlaubten Speichers genutzt werden, bereinige
-        if current_usage > MAX_STORAGE_BYTES * 8 / 10 {
-            println!("⚠️ Speichergrenze erreicht ({}MB) - Starte Bereinigung", 
-                      current_usage / (1024 * 1024));
-            
-            // Bereinige Cache (älteste Dateien zuerst)
-            self.clean_directory(&self.internet_cache, current_usage)?;
-            
-            // Wenn immer noch zu viel, bereinige auch Wissensbasis
-            let new_usage = self.calculate_disk_usage()?;
-            if new_usage > MAX_STORAGE_BYTES * 8 / 10 {
-                self.clean_directory(&self.knowledge_dir, new_usage)?;
+        if !plan.is_empty() {
+            if self.config.storage.dry_run {
+                println!("🔍 Bereinigung (Probelauf), würde löschen: {}", plan);
+            } else {
+                println!("⚠️ Speichergrenze oder Quote erreicht ({}MB) - Starte Bereinigung", 
+                          plan.usage_before / (1024 * 1024));
+                self.live_metrics.storage_cleanups += 1;
+                
+                let (files, bytes) = self.evict(&plan);
+                self.live_metrics.files_evicted += files;
+                self.live_metrics.bytes_evicted += bytes;
+                
+                println!("🧹 Speicherbereinigung abgeschlossen - Neue Nutzung: {}MB", 
+                          self.calculate_disk_usage()? / (1024 * 1024));
             }
-            
-            println!("🧹 Speicherbereinigung abgeschlossen - Neue Nutzung: {}MB", 
-                      self.calculate_disk_usage()? / (1024 * 1024));
+        } else if plan.remaining_over > 0 {
+            println!("⚠️ Speichergrenze erreicht, aber keine löschbaren Dateien ({}KB angeheftet, {}MB in anderen Kategorien)",
+                     plan.pinned_bytes / 1024, plan.unevictable_bytes / (1024 * 1024));
         }
         
+        // Zugriffsverzeichnis sichern
+        let ledger = serde_json::to_vec(&self.access_ledger)?;
+        self.write_guarded(&self.state_dir.join("access_ledger.json"), &ledger)?;
+        
         Ok(())
     }
     
-    /// Bereinigt ein Verzeichnis, beginnend mit den ältesten Dateien
-    pub fn clean_directory(&self, dir: &Path, current_usage: u64) -> Result<(), Box<dyn std::error::Error>> {
-        // Zielgröße: 50% des erlaubten Speichers
-        let target_size = MAX_STORAGE_BYTES / 2;
-        
-        if current_usage <= target_size {
-            return Ok(());
-        }
-        
-        // Sammle alle Dateien mit ihren Metadaten
-        let mut files: Vec<(PathBuf, std::time::SystemTime)> = Vec::new();
-        for entry in fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_file() {
-                if let Ok(metadata) = entry.metadata() {
-                    if let Ok(modified) = metadata.modified() {
-                        files.push((path, modified));
-                    }
-                }
-            }
-        }
+    /// Plant die Bereinigung von Cache und Wissensbasis nach Wert, Quoten und
+    /// angehefteten Mustern aus `storage`, ohne etwas zu löschen
+    pub fn eviction_plan(&mut self) -> Result<EvictionPlan, Box<dyn std::error::Error>> {
+        let usage = self.storage_usage()?;
+        let policy = EvictionPolicy::from_config(&self.config.storage, &self.config.paths.data_root);
         
-        // Sortiere nach Änderungsdatum (älteste zuerst)
-        files.sort_by(|a, b| a.1.cmp(&b.1));
+        let mut items = collect_entries(&self.cache_store, StorageCategory::Cache, &self.access_ledger, &policy);
+        items.extend(collect_entries(&self.knowledge_store, StorageCategory::Knowledge, &self.access_ledger, &policy));
         
-        // Lösche Dateien, bis Zielgröße erreicht ist
-        let mut current = current_usage;
-        for (path, _) in files {
-            if current <= target_size {
-                break;
-            }
-            
-            if let Ok(metadata) = fs::metadata(&path) {
-                let file_size = metadata.len();
-                if let Err(e) = fs::remove_file(&path) {
-                    println!("❌ Fehler beim Löschen von {}: {}", path.display(), e);
-                } else {
-                    current = current.saturating_sub(file_size);
-                    println!("🗑️ Gelöscht: {} ({}KB)", path.display(), file_size / 1024);
+        Ok(policy.plan(items, &usage, chrono::Utc::now().timestamp()))
+    }
+    
+    /// Löscht die Einträge eines Plans. Liefert Anzahl der gelöschten Einträge und
+    /// freigegebene Bytes.
+    pub fn evict(&mut self, plan: &EvictionPlan) -> (u64, u64) {
+        let mut evicted = (0, 0);
+        for eviction in &plan.evictions {
+            let name = eviction.path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            let store = match eviction.category {
+                StorageCategory::Knowledge => &mut self.knowledge_store,
+                _ => &mut self.cache_store,
+            };
+            match store.remove(&name) {
+                Err(e) => println!("❌ Fehler beim Löschen von {}: {}", eviction.path.display(), e),
+                Ok(freed) => {
+                    self.access_ledger.forget(&eviction.path);
+                    evicted.0 += 1;
+                    evicted.1 += freed;
+                    println!("🗑️ Gelöscht: {} ({}, {}KB, Wert {:.4})",
+                             eviction.path.display(), eviction.category.as_str(), eviction.bytes / 1024, eviction.value);
                 }
             }
         }
-        
-        Ok(())
+        self.storage.invalidate(&self.cache_store.root);
+        self.storage.invalidate(&self.knowledge_store.root);
+        evicted
     }
 }
 
@@ -0,0 +1,532 @@
+// src/ast_mutation.rs - Strukturelle Mutationen auf Basis des Rust-Syntaxbaums
+//
+// Alle Operatoren parsen das Genom mit `syn`, verändern genau ein Top-Level-Item
+// und setzen nur dieses Item (neu formatiert via `prettyplease`) wieder in den
+// Originaltext ein. Kommentare außerhalb des veränderten Items bleiben so erhalten.
+use std::ops::Range;
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use rand::{Rng, SeedableRng};
+use syn::visit_mut::{self, VisitMut};
+use syn::{Arm, Attribute, BinOp, Block, ExprBinary, ExprMatch, Item, ItemImpl, ItemMod, Lit, LitFloat, LitInt, Pat, Stmt};
+
+use crate::{EvoliRng, MutationStrategy};
+
+/// Vertauscht zwei benachbarte Anweisungen innerhalb eines Blocks
+pub struct SwapStatementMutation;
+
+/// Verändert Ganzzahl-, Fließkomma- und Wahrheitswert-Literale
+pub struct LiteralMutation;
+
+/// Ersetzt binäre Operatoren durch ihr Gegenstück (`+`/`-`, `<`/`>`, `&&`/`||`, ...)
+pub struct BinaryOperatorMutation;
+
+/// Dupliziert oder entfernt Arme von `match`-Ausdrücken
+pub struct MatchArmMutation;
+
+/// Vertauscht die Reihenfolge benachbarter Items (Top-Level, `impl`- und `mod`-Inhalte)
+pub struct ItemReorderMutation;
+
+impl MutationStrategy for SwapStatementMutation {
+    fn mutate(&self, code: &str, rng: &mut EvoliRng) -> String {
+        mutate_random_site(code, rng, |target, _| SwapStatements { cursor: SiteCursor::new(target) })
+    }
+
+    fn name(&self) -> String {
+        "SwapStatementMutation".to_string()
+    }
+}
+
+impl MutationStrategy for LiteralMutation {
+    fn mutate(&self, code: &str, rng: &mut EvoliRng) -> String {
+        mutate_random_site(code, rng, |target, seed| ChangeLiterals {
+            cursor: SiteCursor::new(target),
+            rng: EvoliRng::seed_from_u64(seed),
+        })
+    }
+
+    fn name(&self) -> String {
+        "LiteralMutation".to_string()
+    }
+}
+
+impl MutationStrategy for BinaryOperatorMutation {
+    fn mutate(&self, code: &str, rng: &mut EvoliRng) -> String {
+        mutate_random_site(code, rng, |target, _| FlipBinaryOperators { cursor: SiteCursor::new(target) })
+    }
+
+    fn name(&self) -> String {
+        "BinaryOperatorMutation".to_string()
+    }
+}
+
+impl MutationStrategy for MatchArmMutation {
+    fn mutate(&self, code: &str, rng: &mut EvoliRng) -> String {
+        mutate_random_site(code, rng, |target, seed| MutateMatchArms {
+            cursor: SiteCursor::new(target),
+            rng: EvoliRng::seed_from_u64(seed),
+        })
+    }
+
+    fn name(&self) -> String {
+        "MatchArmMutation".to_string()
+    }
+}
+
+impl MutationStrategy for ItemReorderMutation {
+    fn mutate(&self, code: &str, rng: &mut EvoliRng) -> String {
+        // Top-Level-Items werden rein textuell vertauscht (keine Neuformatierung),
+        // verschachtelte Items über den Syntaxbaum
+        if rng.gen_bool(0.5) {
+            let swapped = swap_top_level_items(code, rng);
+            if swapped != code {
+                return swapped;
+            }
+        }
+        mutate_random_site(code, rng, |target, _| ReorderNestedItems { cursor: SiteCursor::new(target) })
+    }
+
+    fn name(&self) -> String {
+        "ItemReorderMutation".to_string()
+    }
+}
+
+/// Alle strukturellen Operatoren, z.B. für zusammengesetzte Strategien
+pub fn structural_operators() -> Vec<Box<dyn MutationStrategy>> {
+    vec![
+        Box::new(SwapStatementMutation),
+        Box::new(LiteralMutation),
+        Box::new(BinaryOperatorMutation),
+        Box::new(MatchArmMutation),
+        Box::new(ItemReorderMutation),
+    ]
+}
+
+/// Zählt Mutationsstellen während eines Durchlaufs und markiert die Zielstelle
+struct SiteCursor {
+    target: usize,
+    seen: usize,
+    applied: bool,
+}
+
+impl SiteCursor {
+    fn new(target: usize) -> Self {
+        SiteCursor { target, seen: 0, applied: false }
+    }
+
+    /// Registriert eine Mutationsstelle; `true`, wenn sie verändert werden soll
+    fn hit(&mut self) -> bool {
+        let hit = !self.applied && self.seen == self.target;
+        self.seen += 1;
+        hit
+    }
+}
+
+trait SiteVisitor: VisitMut {
+    fn cursor(&mut self) -> &mut SiteCursor;
+}
+
+/// Wählt zufällig eine Mutationsstelle über alle Top-Level-Items und setzt
+/// das veränderte Item wieder in den Quelltext ein. `make_visitor` erhält die
+/// Zielstelle und einen Seed für eigene Zufallsentscheidungen des Besuchers.
+fn mutate_random_site<V, F>(code: &str, rng: &mut EvoliRng, make_visitor: F) -> String
+where
+    V: SiteVisitor,
+    F: Fn(usize, u64) -> V,
+{
+    let result = with_parsed_file(code, |file| {
+        let counts: Vec<usize> = file
+            .items
+            .iter()
+            .map(|item| {
+                let mut visitor = make_visitor(usize::MAX, 0);
+                visitor.visit_item_mut(&mut item.clone());
+                visitor.cursor().seen
+            })
+            .collect();
+
+        let total: usize = counts.iter().sum();
+        if total == 0 {
+            return None;
+        }
+
+        // Globale Stelle in (Item, lokale Stelle) umrechnen
+        let mut site = rng.gen_range(0..total);
+        let index = counts.iter().position(|&count| {
+            if site < count {
+                true
+            } else {
+                site -= count;
+                false
+            }
+        })?;
+
+        let mut mutated = file.items[index].clone();
+        let mut visitor = make_visitor(site, rng.gen());
+        visitor.visit_item_mut(&mut mutated);
+        if !visitor.cursor().applied {
+            return None;
+        }
+
+        let range = item_byte_range(&file.items[index])?;
+        Some(splice(code, range, &render_item(mutated)))
+    });
+
+    result.unwrap_or_else(|| code.to_string())
+}
+
+/// Vertauscht zwei benachbarte Top-Level-Items, ohne sie neu zu formatieren
+fn swap_top_level_items(code: &str, rng: &mut EvoliRng) -> String {
+    let result = with_parsed_file(code, |file| {
+        // `macro_rules!` ist textuell geordnet und wird daher nicht verschoben
+        let pairs: Vec<usize> = (0..file.items.len().saturating_sub(1))
+            .filter(|&i| !matches!(file.items[i], Item::Macro(_)) && !matches!(file.items[i + 1], Item::Macro(_)))
+            .collect();
+        if pairs.is_empty() {
+            return None;
+        }
+
+        let i = pairs[rng.gen_range(0..pairs.len())];
+        let first = item_byte_range(&file.items[i])?;
+        let second = item_byte_range(&file.items[i + 1])?;
+
+        let mut swapped = String::with_capacity(code.len());
+        swapped.push_str(&code[..first.start]);
+        swapped.push_str(&code[second.clone()]);
+        swapped.push_str(&code[first.end..second.start]);
+        swapped.push_str(&code[first]);
+        swapped.push_str(&code[second.end..]);
+        Some(swapped)
+    });
+
+    result.unwrap_or_else(|| code.to_string())
+}
+
+/// Parst das Genom, führt `f` aus und prüft, dass das Ergebnis wieder gültiges Rust ist
+fn with_parsed_file<F>(code: &str, f: F) -> Option<String>
+where
+    F: FnOnce(&syn::File) -> Option<String>,
+{
+    if !has_plain_prefix(code) {
+        return None;
+    }
+
+    let result = syn::parse_file(code)
+        .ok()
+        .and_then(|file| f(&file))
+        .filter(|mutated| syn::parse_file(mutated).is_ok());
+
+    // Quelltext-Positionen der geparsten Spans wieder freigeben
+    proc_macro2::extra::invalidate_current_thread_spans();
+    result
+}
+
+/// Byte-Offsets der Spans beziehen sich auf den Text ohne BOM/Shebang
+pub(crate) fn has_plain_prefix(code: &str) -> bool {
+    let shebang = code.starts_with("#!") && !code.starts_with("#![");
+    !code.starts_with('\u{feff}') && !shebang
+}
+
+/// Byte-Bereich eines Items im Originaltext (inklusive Attribute und Doc-Kommentare)
+pub(crate) fn item_byte_range(item: &Item) -> Option<Range<usize>> {
+    node_byte_range(item)
+}
+
+/// Byte-Bereich eines beliebigen Syntaxknotens im Originaltext
+pub(crate) fn node_byte_range<T: ToTokens>(node: &T) -> Option<Range<usize>> {
+    let mut tokens = node.to_token_stream().into_iter();
+    let first: Span = tokens.next()?.span();
+    let last = tokens.last().map(|token| token.span()).unwrap_or(first);
+    let range = first.byte_range().start..last.byte_range().end;
+    (range.start < range.end).then_some(range)
+}
+
+fn render_item(item: Item) -> String {
+    let file = syn::File {
+        shebang: None,
+        attrs: Vec::new(),
+        items: vec![item],
+    };
+    prettyplease::unparse(&file).trim_end().to_string()
+}
+
+pub(crate) fn splice(code: &str, range: Range<usize>, replacement: &str) -> String {
+    let mut result = String::with_capacity(code.len() + replacement.len());
+    result.push_str(&code[..range.start]);
+    result.push_str(replacement);
+    result.push_str(&code[range.end..]);
+    result
+}
+
+struct SwapStatements {
+    cursor: SiteCursor,
+}
+
+impl SiteVisitor for SwapStatements {
+    fn cursor(&mut self) -> &mut SiteCursor {
+        &mut self.cursor
+    }
+}
+
+impl VisitMut for SwapStatements {
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        // Der Rückgabeausdruck am Blockende bleibt an seinem Platz
+        let tail = matches!(block.stmts.last(), Some(Stmt::Expr(_, None))) as usize;
+        let swappable = block.stmts.len().saturating_sub(tail);
+        for i in 0..swappable.saturating_sub(1) {
+            if self.cursor.hit() {
+                block.stmts.swap(i, i + 1);
+                self.cursor.applied = true;
+            }
+        }
+        visit_mut::visit_block_mut(self, block);
+    }
+}
+
+struct ChangeLiterals {
+    cursor: SiteCursor,
+    rng: EvoliRng,
+}
+
+impl SiteVisitor for ChangeLiterals {
+    fn cursor(&mut self) -> &mut SiteCursor {
+        &mut self.cursor
+    }
+}
+
+impl VisitMut for ChangeLiterals {
+    fn visit_attribute_mut(&mut self, _attr: &mut Attribute) {
+        // Attribute (cfg, derive, ...) werden nie verändert
+    }
+
+    fn visit_lit_mut(&mut self, lit: &mut Lit) {
+        if !matches!(lit, Lit::Int(_) | Lit::Float(_) | Lit::Bool(_)) || !self.cursor.hit() {
+            return;
+        }
+
+        match lit {
+            Lit::Int(int) => {
+                if let Ok(value) = int.base10_parse::<u128>() {
+                    let candidates = [
+                        value.checked_add(1),
+                        value.checked_sub(1),
+                        value.checked_mul(2),
+                        Some(value / 2),
+                    ];
+                    let new_value = candidates[self.rng.gen_range(0..candidates.len())].filter(|&v| v != value);
+                    if let Some(new_value) = new_value {
+                        *int = LitInt::new(&format!("{}{}", new_value, int.suffix()), int.span());
+                        self.cursor.applied = true;
+                    }
+                }
+            },
+            Lit::Float(float) => {
+                if let Ok(value) = float.base10_parse::<f64>() {
+                    let factors = [0.5, 0.9, 1.1, 2.0];
+                    let new_value = value * factors[self.rng.gen_range(0..factors.len())];
+                    if new_value.is_finite() && new_value != value {
+                        *float = LitFloat::new(&format!("{:?}{}", new_value, float.suffix()), float.span());
+                        self.cursor.applied = true;
+                    }
+                }
+            },
+            Lit::Bool(boolean) => {
+                boolean.value = !boolean.value;
+                self.cursor.applied = true;
+            },
+            _ => {}
+        }
+    }
+}
+
+struct FlipBinaryOperators {
+    cursor: SiteCursor,
+}
+
+impl SiteVisitor for FlipBinaryOperators {
+    fn cursor(&mut self) -> &mut SiteCursor {
+        &mut self.cursor
+    }
+}
+
+/// Gegenstück eines binären Operators, falls eines definiert ist
+fn flipped_operator(op: &BinOp) -> Option<BinOp> {
+    let flipped = match op {
+        BinOp::Add(t) => BinOp::Sub(syn::Token![-](t.span)),
+        BinOp::Sub(t) => BinOp::Add(syn::Token![+](t.span)),
+        BinOp::Mul(t) => BinOp::Div(syn::Token![/](t.span)),
+        BinOp::Div(t) => BinOp::Mul(syn::Token![*](t.span)),
+        BinOp::Lt(t) => BinOp::Gt(syn::Token![>](t.span)),
+        BinOp::Gt(t) => BinOp::Lt(syn::Token![<](t.span)),
+        BinOp::Le(t) => BinOp::Ge(syn::Token![>=](t.spans)),
+        BinOp::Ge(t) => BinOp::Le(syn::Token![<=](t.spans)),
+        BinOp::Eq(t) => BinOp::Ne(syn::Token![!=](t.spans)),
+        BinOp::Ne(t) => BinOp::Eq(syn::Token![==](t.spans)),
+        BinOp::And(t) => BinOp::Or(syn::Token![||](t.spans)),
+        BinOp::Or(t) => BinOp::And(syn::Token![&&](t.spans)),
+        BinOp::BitAnd(t) => BinOp::BitOr(syn::Token![|](t.span)),
+        BinOp::BitOr(t) => BinOp::BitAnd(syn::Token![&](t.span)),
+        BinOp::AddAssign(t) => BinOp::SubAssign(syn::Token![-=](t.spans)),
+        BinOp::SubAssign(t) => BinOp::AddAssign(syn::Token![+=](t.spans)),
+        BinOp::MulAssign(t) => BinOp::DivAssign(syn::Token![/=](t.spans)),
+        BinOp::DivAssign(t) => BinOp::MulAssign(syn::Token![*=](t.spans)),
+        _ => return None,
+    };
+    Some(flipped)
+}
+
+impl VisitMut for FlipBinaryOperators {
+    fn visit_expr_binary_mut(&mut self, expr: &mut ExprBinary) {
+        if let Some(flipped) = flipped_operator(&expr.op) {
+            if self.cursor.hit() {
+                expr.op = flipped;
+                self.cursor.applied = true;
+            }
+        }
+        visit_mut::visit_expr_binary_mut(self, expr);
+    }
+}
+
+struct MutateMatchArms {
+    cursor: SiteCursor,
+    rng: EvoliRng,
+}
+
+impl SiteVisitor for MutateMatchArms {
+    fn cursor(&mut self) -> &mut SiteCursor {
+        &mut self.cursor
+    }
+}
+
+/// Ein Arm, der alle verbleibenden Werte abdeckt (`_` oder reine Bindung ohne Guard)
+fn is_catch_all(arm: &Arm) -> bool {
+    arm.guard.is_none()
+        && match &arm.pat {
+            Pat::Wild(_) => true,
+            Pat::Ident(ident) => ident.subpat.is_none(),
+            _ => false,
+        }
+}
+
+impl VisitMut for MutateMatchArms {
+    fn visit_expr_match_mut(&mut self, expr: &mut ExprMatch) {
+        let arms = expr.arms.len();
+        if arms >= 2 {
+            // Duplizieren: Arm i wird vor einen früheren Arm kopiert und verdeckt ihn
+            for i in 1..arms {
+                if self.cursor.hit() {
+                    let position = self.rng.gen_range(0..i);
+                    let copy = expr.arms[i].clone();
+                    expr.arms.insert(position, copy);
+                    self.cursor.applied = true;
+                }
+            }
+
+            // Entfernen: nur solange ein abschließender Auffang-Arm Vollständigkeit garantiert
+            if !self.cursor.applied && is_catch_all(&expr.arms[arms - 1]) {
+                for i in 0..arms - 1 {
+                    if self.cursor.hit() {
+                        expr.arms.remove(i);
+                        self.cursor.applied = true;
+                    }
+                }
+            }
+        }
+        visit_mut::visit_expr_match_mut(self, expr);
+    }
+}
+
+struct ReorderNestedItems {
+    cursor: SiteCursor,
+}
+
+impl SiteVisitor for ReorderNestedItems {
+    fn cursor(&mut self) -> &mut SiteCursor {
+        &mut self.cursor
+    }
+}
+
+impl VisitMut for ReorderNestedItems {
+    fn visit_item_impl_mut(&mut self, item: &mut ItemImpl) {
+        for i in 0..item.items.len().saturating_sub(1) {
+            if self.cursor.hit() {
+                item.items.swap(i, i + 1);
+                self.cursor.applied = true;
+            }
+        }
+        visit_mut::visit_item_impl_mut(self, item);
+    }
+
+    fn visit_item_mod_mut(&mut self, item: &mut ItemMod) {
+        if let Some((_, items)) = item.content.as_mut() {
+            for i in 0..items.len().saturating_sub(1) {
+                if matches!(items[i], Item::Macro(_)) || matches!(items[i + 1], Item::Macro(_)) {
+                    continue;
+                }
+                if self.cursor.hit() {
+                    items.swap(i, i + 1);
+                    self.cursor.applied = true;
+                }
+            }
+        }
+        visit_mut::visit_item_mod_mut(self, item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wendet eine zufällige Strategie wiederholt an, bis sich der Code ändert
+    fn mutate_until_changed(strategy: &dyn MutationStrategy, code: &str) -> String {
+        let mut rng = EvoliRng::seed_from_u64(7);
+        for _ in 0..50 {
+            let mutated = strategy.mutate(code, &mut rng);
+            if mutated != code {
+                return mutated;
+            }
+        }
+        panic!("{} hat den Code nie verändert", strategy.name());
+    }
+
+    #[test]
+    fn test_binary_operator_flip() {
+        let code = "// Kommentar bleibt\nfn summe() -> i32 {\n    1 + 2\n}\n";
+        let mutated = BinaryOperatorMutation.mutate(code, &mut EvoliRng::seed_from_u64(1));
+        assert!(mutated.contains("1 - 2"));
+        assert!(mutated.starts_with("// Kommentar bleibt"));
+    }
+
+    #[test]
+    fn test_structural_operators_emit_valid_rust() {
+        let code = "fn klassifiziere(x: u32) -> &'static str {\n\
+                    \x20   let grenze = 10;\n\
+                    \x20   let aktiv = true;\n\
+                    \x20   match x {\n\
+                    \x20       0 => \"null\",\n\
+                    \x20       1 if aktiv => \"eins\",\n\
+                    \x20       _ if x < grenze => \"klein\",\n\
+                    \x20       _ => \"groß\",\n\
+                    \x20   }\n\
+                    }\n\
+                    struct A;\n\
+                    impl A {\n\
+                    \x20   fn a(&self) {}\n\
+                    \x20   fn b(&self) {}\n\
+                    }\n";
+
+        for strategy in structural_operators() {
+            let mutated = mutate_until_changed(strategy.as_ref(), code);
+            assert!(syn::parse_file(&mutated).is_ok(), "{} erzeugte ungültigen Code", strategy.name());
+        }
+    }
+
+    #[test]
+    fn test_unparsable_code_is_left_unchanged() {
+        let code = "fn kaputt( {";
+        for strategy in structural_operators() {
+            assert_eq!(strategy.mutate(code, &mut EvoliRng::seed_from_u64(1)), code);
+        }
+    }
+}
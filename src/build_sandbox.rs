@@ -0,0 +1,297 @@
+// src/build_sandbox.rs - Isolierter Build von Kandidaten in eigenen Cargo-Projekten
+//
+// Jeder Kandidat wird in ein temporäres Cargo-Projekt mit derselben Manifest-
+// Konfiguration (Paketname, Abhängigkeiten, Cargo.lock) wie das echte Projekt
+// geschrieben und offline gebaut. Die Modul-Dateien neben dem Kern werden
+// mitkopiert, sodass ein Genom gleich `src/Evoli_Kern.rs` tatsächlich kompiliert.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::diagnostics::{parse_cargo_messages, Diagnostic};
+use crate::execution_sandbox::{ExecutionResult, ExecutionSandbox};
+
+/// Zähler für eindeutige Arbeitsverzeichnisse innerhalb eines Prozesses
+static WORKSPACE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Ergebnis eines Kandidaten-Builds
+#[derive(Clone, Debug, Default)]
+pub struct BuildResult {
+    pub success: bool,
+    pub duration: Duration,
+    pub warning_count: u64,
+    pub artifact_size: u64, // Größe der erzeugten Bibliothek in Bytes
+    pub stderr: String,
+    pub diagnostics: Vec<Diagnostic>,
+    pub tests: Option<TestOutcome>,
+}
+
+impl BuildResult {
+    /// Formatierte Fehlermeldungen des Compilers (oder stderr, falls keine vorliegen)
+    pub fn error_report(&self) -> String {
+        let rendered: Vec<&str> = self.diagnostics.iter()
+            .filter(|diagnostic| diagnostic.is_error())
+            .map(|diagnostic| diagnostic.rendered.as_str())
+            .collect();
+        if rendered.is_empty() {
+            self.stderr.clone()
+        } else {
+            rendered.concat()
+        }
+    }
+}
+
+/// Ergebnis des Testlaufs eines erfolgreich gebauten Kandidaten
+#[derive(Clone, Debug, Default)]
+pub struct TestOutcome {
+    pub passed: u64,
+    pub failed: u64,
+    pub duration: Duration,
+    pub execution: ExecutionResult, // Exit-Status, Ausgabe und Ressourcenverbrauch der Test-Binärdatei
+}
+
+/// Baut Kandidaten-Genome in temporären Cargo-Projekten
+pub struct BuildSandbox {
+    manifest: toml::Table,               // Manifest für die Kandidatenprojekte
+    lockfile: Option<String>,            // Cargo.lock des echten Projekts
+    support_files: BTreeMap<String, String>, // Weitere Quelltexte (Module) neben dem Genom
+    lib_name: String,
+    pub target_dir: PathBuf,             // Gemeinsames Build-Verzeichnis aller Kandidaten
+    pub workspace_root: PathBuf,         // Hier werden die temporären Projekte angelegt
+    pub offline: bool,
+    pub run_tests: bool,
+    pub runner: ExecutionSandbox,        // Führt die Test-Binärdateien der Kandidaten aus
+}
+
+impl BuildSandbox {
+    /// Übernimmt Manifest, Cargo.lock und Modul-Dateien aus dem Projekt unter `root`;
+    /// gebaut wird nach `target_dir` (relativ zum Arbeitsverzeichnis des Prozesses)
+    pub fn from_project(root: &Path, target_dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let root = root.canonicalize()?;
+        let original: toml::Table = fs::read_to_string(root.join("Cargo.toml"))?.parse()?;
+
+        let lib = original.get("lib").and_then(|lib| lib.as_table());
+        let lib_path = lib
+            .and_then(|lib| lib.get("path"))
+            .and_then(|path| path.as_str())
+            .unwrap_or("src/lib.rs");
+        let package_name = original
+            .get("package")
+            .and_then(|package| package.get("name"))
+            .and_then(|name| name.as_str())
+            .unwrap_or("evoli_candidate");
+        let lib_name = lib
+            .and_then(|lib| lib.get("name"))
+            .and_then(|name| name.as_str())
+            .unwrap_or(package_name)
+            .replace('-', "_");
+
+        // Kandidatenmanifest: gleiches Paket und gleiche Abhängigkeiten, nur die Bibliothek
+        let mut manifest = toml::Table::new();
+        for section in ["package", "dependencies", "dev-dependencies", "target", "features"] {
+            if let Some(value) = original.get(section) {
+                manifest.insert(section.to_string(), value.clone());
+            }
+        }
+        let mut lib_table = toml::Table::new();
+        lib_table.insert("name".to_string(), toml::Value::String(lib_name.clone()));
+        lib_table.insert("path".to_string(), toml::Value::String("src/lib.rs".to_string()));
+        manifest.insert("lib".to_string(), toml::Value::Table(lib_table));
+        manifest.insert("workspace".to_string(), toml::Value::Table(toml::Table::new()));
+
+        // Alle übrigen Quelltexte im Verzeichnis der Bibliothek (ohne Binärdateien)
+        let lib_file = root.join(lib_path);
+        let mut support_files = BTreeMap::new();
+        if let Some(source_dir) = lib_file.parent() {
+            for entry in fs::read_dir(source_dir)? {
+                let path = entry?.path();
+                let is_rust = path.extension().map(|ext| ext == "rs").unwrap_or(false);
+                let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default().to_string();
+                if is_rust && path != lib_file && file_name != "main.rs" && file_name != "lib.rs" {
+                    support_files.insert(file_name, fs::read_to_string(&path)?);
+                }
+            }
+        }
+
+        Ok(BuildSandbox {
+            manifest,
+            lockfile: fs::read_to_string(root.join("Cargo.lock")).ok(),
+            support_files,
+            lib_name,
+            target_dir: std::path::absolute(target_dir)?,
+            workspace_root: std::env::temp_dir(),
+            offline: true,
+            run_tests: true,
+            runner: ExecutionSandbox::default(),
+        })
+    }
+
+    /// Legt ein temporäres Cargo-Projekt für das Genom an
+    pub fn materialize(&self, genome: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let workspace = self.workspace_root.join(format!(
+            "evoli_candidate_{}_{}",
+            std::process::id(),
+            WORKSPACE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let source_dir = workspace.join("src");
+        fs::create_dir_all(&source_dir)?;
+
+        fs::write(workspace.join("Cargo.toml"), toml::to_string(&self.manifest)?)?;
+        if let Some(lockfile) = &self.lockfile {
+            fs::write(workspace.join("Cargo.lock"), lockfile)?;
+        }
+        for (name, content) in &self.support_files {
+            fs::write(source_dir.join(name), content)?;
+        }
+        fs::write(source_dir.join("lib.rs"), genome)?;
+
+        Ok(workspace)
+    }
+
+    /// Prüft ein Genom mit `cargo check` und liefert nur die Diagnosen
+    pub fn check(&self, genome: &str) -> Result<Vec<Diagnostic>, Box<dyn std::error::Error>> {
+        let workspace = self.materialize(genome)?;
+        let output = self.cargo(&workspace, &["check", "--lib", "--message-format=json"]).output();
+        fs::remove_dir_all(&workspace).ok();
+
+        let stdout = String::from_utf8_lossy(&output?.stdout).into_owned();
+        Ok(parse_cargo_messages(&stdout, &self.lib_name).diagnostics)
+    }
+
+    /// Baut (und testet) ein Genom; das temporäre Projekt wird danach entfernt
+    pub fn build(&self, genome: &str) -> Result<BuildResult, Box<dyn std::error::Error>> {
+        let workspace = self.materialize(genome)?;
+        let result = self.build_workspace(&workspace);
+        fs::remove_dir_all(&workspace).ok();
+        result
+    }
+
+    fn build_workspace(&self, workspace: &Path) -> Result<BuildResult, Box<dyn std::error::Error>> {
+        let mut result = BuildResult::default();
+
+        let start = Instant::now();
+        let output = self.cargo(workspace, &["build", "--lib", "--message-format=json"]).output()?;
+        result.duration = start.elapsed();
+        result.success = output.status.success();
+        result.stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+
+        let messages = parse_cargo_messages(&String::from_utf8_lossy(&output.stdout), &self.lib_name);
+        result.warning_count = messages.diagnostics.iter().filter(|d| d.is_warning()).count() as u64;
+        result.diagnostics = messages.diagnostics;
+
+        if !result.success {
+            return Ok(result);
+        }
+
+        result.artifact_size = messages.artifacts.iter()
+            .filter(|file| file.ends_with(".rlib"))
+            .filter_map(|file| fs::metadata(file).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+
+        if self.run_tests {
+            result.tests = self.test_workspace(workspace);
+        }
+
+        Ok(result)
+    }
+
+    /// Übersetzt zuerst die Tests und führt die Test-Binärdatei dann in der
+    /// Ausführungs-Sandbox aus; gemessen wird nur die Ausführung
+    fn test_workspace(&self, workspace: &Path) -> Option<TestOutcome> {
+        let compiled = self.cargo(workspace, &["test", "--lib", "--no-run", "--message-format=json"]).output().ok()?;
+        if !compiled.status.success() {
+            return None;
+        }
+        let messages = parse_cargo_messages(&String::from_utf8_lossy(&compiled.stdout), &self.lib_name);
+        let executable = messages.executables.first()?;
+
+        let execution = match self.runner.run(Path::new(executable), &["--test-threads=1"]) {
+            Ok(execution) => execution,
+            Err(e) => {
+                println!("⚠️ Test-Binärdatei konnte nicht ausgeführt werden: {}", e);
+                return None;
+            }
+        };
+        let (passed, failed) = parse_test_summary(&execution.stdout);
+
+        Some(TestOutcome { passed, failed, duration: execution.wall_time, execution })
+    }
+
+    fn cargo(&self, workspace: &Path, args: &[&str]) -> Command {
+        let mut command = Command::new("cargo");
+        command
+            .args(args)
+            .arg("--manifest-path")
+            .arg(workspace.join("Cargo.toml"))
+            .env("CARGO_TARGET_DIR", &self.target_dir)
+            .current_dir(workspace);
+        if self.offline {
+            command.arg("--offline");
+        }
+        if self.lockfile.is_some() {
+            command.arg("--locked");
+        }
+        command
+    }
+}
+
+/// Liest "test result: ... N passed; M failed" aus der Ausgabe des Test-Runners
+fn parse_test_summary(stdout: &str) -> (u64, u64) {
+    let mut passed = 0;
+    let mut failed = 0;
+
+    for line in stdout.lines().filter(|line| line.starts_with("test result:")) {
+        for part in line.split(';') {
+            let words: Vec<&str> = part.split_whitespace().collect();
+            for pair in words.windows(2) {
+                match pair[1] {
+                    "passed" => passed += pair[0].parse::<u64>().unwrap_or(0),
+                    "failed" => failed += pair[0].parse::<u64>().unwrap_or(0),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    (passed, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandbox_builds_and_tests_candidate() {
+        let project = std::env::temp_dir().join(format!("evoli_sandbox_project_{}", std::process::id()));
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::write(project.join("Cargo.toml"), "[package]\nname = \"probe\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[lib]\npath = \"src/kern.rs\"\n").unwrap();
+        fs::write(project.join("src").join("kern.rs"), "mod hilfe;\n").unwrap();
+        fs::write(project.join("src").join("hilfe.rs"), "pub fn eins() -> u8 { 1 }\n").unwrap();
+
+        let sandbox = BuildSandbox::from_project(&project, &project.join("target")).unwrap();
+
+        let genome = "mod hilfe;\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn ok() { assert_eq!(super::hilfe::eins(), 1); }\n}\n";
+        let result = sandbox.build(genome).unwrap();
+        assert!(result.success, "{}", result.stderr);
+        assert!(result.artifact_size > 0);
+        let tests = result.tests.unwrap();
+        assert_eq!((tests.passed, tests.failed), (1, 0));
+        assert_eq!(tests.execution.exit_code, Some(0));
+
+        let broken = sandbox.build("pub fn kaputt() -> u8 { \"text\" }").unwrap();
+        assert!(!broken.success);
+        assert_eq!(broken.diagnostics[0].code.as_deref(), Some("E0308"));
+
+        fs::remove_dir_all(&project).ok();
+    }
+
+    #[test]
+    fn test_parse_test_summary() {
+        let stdout = "running 3 tests\ntest result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured\n";
+        assert_eq!(parse_test_summary(stdout), (2, 1));
+    }
+}
@@ -0,0 +1,263 @@
+// src/capabilities.rs - Statische Fähigkeitsanalyse von Kandidaten-Genomen
+//
+// Ein Genom wird mit `syn` auf Gültigkeit geprüft und anschließend als
+// Tokenstrom durchsucht, damit auch Aufrufe innerhalb von Makros erfasst
+// werden. Jede Fundstelle eines sensiblen Pfads (z.B. `fs::remove_file`,
+// `Command`, `reqwest`) zählt für ihre Fähigkeit. Ein Kandidat eskaliert,
+// wenn er ein Symbol häufiger verwendet als das Ausgangsgenom.
+use std::collections::BTreeMap;
+
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+
+use crate::safety::{PolicyAction, SafetyRule};
+
+/// Sicherheitsrelevante Fähigkeit eines Genoms
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Capability {
+    ProcessSpawn,
+    FsWrite,
+    FsDelete,
+    Network,
+    Unsafe,
+    Ffi,
+}
+
+/// Pfadmuster je Fähigkeit; ein Muster trifft, wenn es zusammenhängend im Pfad vorkommt
+const PATTERNS: &[(Capability, &[&str])] = &[
+    (Capability::ProcessSpawn, &["Command"]),
+    (Capability::ProcessSpawn, &["fork"]),
+    (Capability::ProcessSpawn, &["execvp"]),
+    (Capability::FsWrite, &["fs", "write"]),
+    (Capability::FsWrite, &["File", "create"]),
+    (Capability::FsWrite, &["OpenOptions"]),
+    (Capability::FsWrite, &["create_dir"]),
+    (Capability::FsWrite, &["create_dir_all"]),
+    (Capability::FsWrite, &["fs", "rename"]),
+    (Capability::FsWrite, &["fs", "copy"]),
+    (Capability::FsWrite, &["set_permissions"]),
+    (Capability::FsWrite, &["hard_link"]),
+    (Capability::FsWrite, &["symlink"]),
+    (Capability::FsDelete, &["remove_file"]),
+    (Capability::FsDelete, &["remove_dir"]),
+    (Capability::FsDelete, &["remove_dir_all"]),
+    (Capability::Network, &["reqwest"]),
+    (Capability::Network, &["TcpStream"]),
+    (Capability::Network, &["TcpListener"]),
+    (Capability::Network, &["UdpSocket"]),
+    (Capability::Network, &["ToSocketAddrs"]),
+    (Capability::Unsafe, &["unsafe"]),
+    (Capability::Ffi, &["extern"]),
+    (Capability::Ffi, &["libc"]),
+    (Capability::Ffi, &["no_mangle"]),
+    (Capability::Ffi, &["link_name"]),
+];
+
+/// Fundstellen je Fähigkeit und Symbol (z.B. `FsDelete` → `remove_file` → 2)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CapabilityManifest {
+    pub uses: BTreeMap<Capability, BTreeMap<String, usize>>,
+}
+
+/// Ein Symbol, das der Kandidat häufiger verwendet als die Basislinie
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapabilityEscalation {
+    pub capability: Capability,
+    pub symbol: String,
+    pub baseline: usize,
+    pub candidate: usize,
+}
+
+impl std::fmt::Display for CapabilityEscalation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} `{}` {}→{}", self.capability, self.symbol, self.baseline, self.candidate)
+    }
+}
+
+impl CapabilityManifest {
+    /// Analysiert `code`; `None`, wenn der Code kein gültiges Rust ist
+    pub fn scan(code: &str) -> Option<Self> {
+        syn::parse_file(code).ok()?;
+        let tokens: TokenStream = code.parse().ok()?;
+
+        let mut paths = Vec::new();
+        collect_paths(tokens, &mut paths);
+
+        let mut manifest = CapabilityManifest::default();
+        for path in &paths {
+            for (capability, pattern) in PATTERNS {
+                if path.windows(pattern.len()).any(|window| window == *pattern) {
+                    *manifest.uses
+                        .entry(*capability)
+                        .or_default()
+                        .entry(pattern.join("::"))
+                        .or_default() += 1;
+                }
+            }
+        }
+        Some(manifest)
+    }
+
+    /// Gesamtzahl der Fundstellen einer Fähigkeit
+    pub fn count(&self, capability: Capability) -> usize {
+        self.uses.get(&capability).map(|symbols| symbols.values().sum()).unwrap_or(0)
+    }
+
+    /// Alle Symbole, die `self` häufiger verwendet als `baseline`
+    pub fn escalations_over(&self, baseline: &CapabilityManifest) -> Vec<CapabilityEscalation> {
+        let mut escalations = Vec::new();
+        for (capability, symbols) in &self.uses {
+            for (symbol, &candidate) in symbols {
+                let allowed = baseline.uses
+                    .get(capability)
+                    .and_then(|symbols| symbols.get(symbol))
+                    .copied()
+                    .unwrap_or(0);
+                if candidate > allowed {
+                    escalations.push(CapabilityEscalation {
+                        capability: *capability,
+                        symbol: symbol.clone(),
+                        baseline: allowed,
+                        candidate,
+                    });
+                }
+            }
+        }
+        escalations
+    }
+}
+
+/// Zerlegt den Tokenstrom (rekursiv, inklusive Makro-Argumenten) in `a::b::c`-Pfade
+fn collect_paths(tokens: TokenStream, paths: &mut Vec<Vec<String>>) {
+    let mut current: Vec<String> = Vec::new();
+    let mut separator = false; // Zuletzt `::` gelesen
+    let mut pending_colon = false;
+
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                if !separator && !current.is_empty() {
+                    paths.push(std::mem::take(&mut current));
+                }
+                current.push(ident.to_string());
+                separator = false;
+                pending_colon = false;
+            },
+            TokenTree::Punct(punct) if punct.as_char() == ':' => {
+                if pending_colon {
+                    separator = true;
+                    pending_colon = false;
+                } else {
+                    pending_colon = punct.spacing() == Spacing::Joint;
+                }
+            },
+            TokenTree::Group(group) => {
+                if !current.is_empty() {
+                    paths.push(std::mem::take(&mut current));
+                }
+                separator = false;
+                pending_colon = false;
+                collect_paths(group.stream(), paths);
+            },
+            _ => {
+                if !current.is_empty() {
+                    paths.push(std::mem::take(&mut current));
+                }
+                separator = false;
+                pending_colon = false;
+            },
+        }
+    }
+
+    if !current.is_empty() {
+        paths.push(current);
+    }
+}
+
+/// Blockiert Kandidaten, die mehr Fähigkeiten nutzen als das Ausgangsgenom
+pub struct NoCapabilityEscalationRule {
+    pub baseline: CapabilityManifest,
+}
+
+impl NoCapabilityEscalationRule {
+    /// Verwendet die Fähigkeiten von `genome` als erlaubte Basislinie
+    pub fn from_genome(genome: &str) -> Self {
+        NoCapabilityEscalationRule {
+            baseline: CapabilityManifest::scan(genome).unwrap_or_default(),
+        }
+    }
+
+    /// Begründung, falls `genome` gegenüber der Basislinie eskaliert
+    pub fn escalation_report(&self, genome: &str) -> Option<String> {
+        let Some(manifest) = CapabilityManifest::scan(genome) else {
+            return Some("Genom ist kein gültiges Rust und kann nicht analysiert werden".to_string());
+        };
+        let escalations = manifest.escalations_over(&self.baseline);
+        if escalations.is_empty() {
+            return None;
+        }
+        let details: Vec<String> = escalations.iter().map(|e| e.to_string()).collect();
+        Some(format!("neue Fähigkeiten: {}", details.join(", ")))
+    }
+}
+
+impl SafetyRule for NoCapabilityEscalationRule {
+    fn check(&self, action: &PolicyAction) -> Result<(), String> {
+        match action {
+            PolicyAction::EvaluateCandidate { genome } | PolicyAction::AdoptGenome { genome, .. } => {
+                match self.escalation_report(genome) {
+                    Some(reason) => Err(reason),
+                    None => Ok(()),
+                }
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn name(&self) -> String {
+        "no_capability_escalation".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASELINE: &str = r#"
+        use std::fs;
+        pub fn speichern(pfad: &str) -> std::io::Result<()> {
+            fs::write(pfad, "daten")?;
+            println!("{}", pfad);
+            Ok(())
+        }
+    "#;
+
+    #[test]
+    fn test_manifest_counts_paths_and_macro_arguments() {
+        let manifest = CapabilityManifest::scan(BASELINE).unwrap();
+        assert_eq!(manifest.count(Capability::FsWrite), 1);
+        assert_eq!(manifest.count(Capability::ProcessSpawn), 0);
+
+        let hidden = r#"pub fn f() { let _ = vec![std::process::Command::new("sh")]; unsafe { g() } }
+                        unsafe fn g() {}"#;
+        let manifest = CapabilityManifest::scan(hidden).unwrap();
+        assert_eq!(manifest.count(Capability::ProcessSpawn), 1);
+        assert_eq!(manifest.count(Capability::Unsafe), 2);
+
+        assert!(CapabilityManifest::scan("pub fn kaputt( {").is_none());
+    }
+
+    #[test]
+    fn test_rule_rejects_escalating_candidates() {
+        let rule = NoCapabilityEscalationRule::from_genome(BASELINE);
+        let unchanged = BASELINE.replace("daten", "andere daten");
+        assert!(rule.check(&PolicyAction::EvaluateCandidate { genome: &unchanged }).is_ok());
+
+        let deleting = format!("{}\npub fn weg() {{ let _ = std::fs::remove_file(\"/\"); }}", BASELINE);
+        let reason = rule.check(&PolicyAction::EvaluateCandidate { genome: &deleting }).unwrap_err();
+        assert!(reason.contains("remove_file"), "{}", reason);
+
+        let twice = BASELINE.replace("fs::write(pfad, \"daten\")?;", "fs::write(pfad, \"a\")?; fs::write(pfad, \"b\")?;");
+        let adopt = PolicyAction::AdoptGenome { genome: &twice, compiles: true, backed_up: true };
+        assert!(rule.check(&adopt).unwrap_err().contains("`fs::write` 1→2"));
+    }
+}
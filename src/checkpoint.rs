@@ -0,0 +1,126 @@
+// src/checkpoint.rs - Versionierter Zustands-Checkpoint des Kerns
+//
+// Der Checkpoint enthält alles, was nicht aus dem Quellcode oder anderen
+// persistenten Dateien (Pareto-Archiv, Genomspeicher, Sicherheitsprotokoll)
+// wiederhergestellt werden kann. Er wird als JSON atomar geschrieben (temporäre
+// Datei + rename), damit ein Absturz beim Speichern den letzten Stand nicht zerstört.
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostics::DiagnosticStats;
+use crate::population::{Individual, PopulationConfig};
+use crate::RuntimeMetrics;
+
+/// Aktuelle Version des Checkpoint-Formats. Version 1 enthielt zusätzlich
+/// `evolution_backups`; diese liegen inzwischen im Genomspeicher und werden ignoriert.
+pub const CHECKPOINT_VERSION: u32 = 2;
+
+/// Serialisierbarer Zustand von `EnhancedEvoliKern`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KernelCheckpoint {
+    pub version: u32,
+    pub saved_at: String,
+    pub generation: u64,
+    pub fitness_score: f64,
+    pub incumbent_evaluated: bool,
+    pub uptime: Duration,
+    pub primary_genome: String,
+    pub module_genomes: HashMap<String, String>,
+    pub individuals: Vec<Individual>,
+    pub population_config: PopulationConfig,
+    pub fitness_weights: HashMap<String, f64>,
+    pub modification_rules: Vec<String>,
+    pub developed_strategies: Vec<String>, // Namen selbstentwickelter Mutationsstrategien
+    pub diagnostic_stats: DiagnosticStats,
+    pub internet_enabled: bool,
+    #[serde(default)]
+    pub metrics: RuntimeMetrics, // Fortlaufende Zähler des Kerns
+    #[serde(default)]
+    pub seed: Option<u64>,       // Seed des Laufs; fehlt in älteren Checkpoints
+}
+
+impl KernelCheckpoint {
+    /// Lädt einen Checkpoint; neuere Formatversionen werden abgelehnt
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(path)?;
+        let value: serde_json::Value = serde_json::from_str(&content)?;
+
+        let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+        if version == 0 || version > CHECKPOINT_VERSION {
+            return Err(format!(
+                "Checkpoint {} hat Formatversion {}, unterstützt wird {}",
+                path.display(), version, CHECKPOINT_VERSION
+            ).into());
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Schreibt den Checkpoint atomar nach `path`
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temporary = path.with_extension("json.tmp");
+        fs::write(&temporary, serde_json::to_string(self)?)?;
+        fs::rename(&temporary, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint() -> KernelCheckpoint {
+        KernelCheckpoint {
+            version: CHECKPOINT_VERSION,
+            saved_at: "2026-01-01 00:00:00".to_string(),
+            generation: 42,
+            fitness_score: 0.75,
+            incumbent_evaluated: true,
+            uptime: Duration::from_secs(3600),
+            primary_genome: "pub fn a() {}".to_string(),
+            module_genomes: HashMap::from([("evoli_module_x".to_string(), "// x".to_string())]),
+            individuals: vec![Individual {
+                genome: "pub fn a() {}".to_string(),
+                fitness: 0.75,
+                objectives: vec![0.5],
+                generation: 41,
+                origin: "LiteralMutation".to_string(),
+            }],
+            population_config: PopulationConfig::default(),
+            fitness_weights: HashMap::from([("BinarySize".to_string(), 2.0)]),
+            modification_rules: Vec::new(),
+            developed_strategies: vec!["EvolvdStrategy_7".to_string()],
+            diagnostic_stats: DiagnosticStats::default(),
+            internet_enabled: false,
+            metrics: RuntimeMetrics { successful_compilations: 12, internet_requests: 3, ..RuntimeMetrics::default() },
+            seed: Some(1234),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_and_version_check() {
+        let path = std::env::temp_dir().join(format!("evoli_checkpoint_test_{}.json", std::process::id()));
+
+        checkpoint().save(&path).unwrap();
+        let loaded = KernelCheckpoint::load(&path).unwrap();
+        assert_eq!(loaded.generation, 42);
+        assert_eq!(loaded.individuals[0].origin, "LiteralMutation");
+        assert_eq!(loaded.developed_strategies, vec!["EvolvdStrategy_7".to_string()]);
+        assert_eq!(loaded.uptime, Duration::from_secs(3600));
+        assert_eq!((loaded.metrics.successful_compilations, loaded.metrics.internet_requests), (12, 3));
+        assert_eq!(loaded.seed, Some(1234));
+
+        let newer = KernelCheckpoint { version: CHECKPOINT_VERSION + 1, ..checkpoint() };
+        newer.save(&path).unwrap();
+        assert!(KernelCheckpoint::load(&path).is_err());
+
+        fs::remove_file(&path).ok();
+    }
+}
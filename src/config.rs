@@ -0,0 +1,625 @@
+// src/config.rs - TOML-Konfiguration mit Profilen und Umgebungsvariablen
+//
+// Die Werte werden in dieser Reihenfolge überlagert (spätere gewinnen):
+//   1. eingebaute Standardwerte (entsprechen dem bisherigen Verhalten)
+//   2. die Konfigurationsdatei (`--config`, `EVOLI_CONFIG` oder `evoli.toml`, falls vorhanden)
+//   3. ein Profil (`--profile`, `EVOLI_PROFILE` oder `profile = "..."` in der Datei);
+//      eingebaut sind `dev`, `long-run` und `offline`, eigene stehen unter `[profiles.<name>]`
+//   4. Umgebungsvariablen `EVOLI_<ABSCHNITT>__<SCHLÜSSEL>`, z.B. `EVOLI_STORAGE__MAX_BYTES=1073741824`
+//      oder `EVOLI_PATHS__DATA_ROOT=/var/lib/evoli/a`; Werte werden als TOML gelesen, sonst als Text
+//
+// Beispiel:
+//
+//   profile = "long-run"
+//   [paths]
+//   data_root = "/var/lib/evoli/instanz_a"
+//   [storage]
+//   max_bytes = 10_737_418_240
+//   [evolution.fitness_weights]
+//   CompileTime = 0.0
+//   TestPassRatio = 2.0
+//   [profiles.long-run.evolution]
+//   cycle_interval_secs = 7200
+//
+// Zur Laufzeit kann die Konfiguration neu geladen werden (SIGHUP); `changes_to`
+// beschreibt die Unterschiede, Pfade, Metrik-Adresse und Seed gelten erst nach einem Neustart.
+// Ein Seed aus einem vorhandenen Checkpoint hat Vorrang vor `evolution.seed`.
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+use crate::crawler::CrawlConfig;
+use crate::eviction::EVICTABLE_CATEGORIES;
+use crate::fitness::default_evaluators;
+use crate::population::{PopulationConfig, ReplacementStrategy};
+use crate::safety::domain_allowed;
+use crate::MAX_STORAGE_BYTES;
+
+/// Wird ohne `--config`/`EVOLI_CONFIG` gelesen, sofern vorhanden
+pub const DEFAULT_CONFIG_FILE: &str = "evoli.toml";
+
+/// Einstellungen, die nur beim Start gelesen werden (Präfixe der Schlüssel)
+const RESTART_REQUIRED: &[&str] = &["paths.", "interface.metrics_addr", "evolution.seed", "learning.transport", "learning.fixture_dir"];
+
+/// Mitgelieferte Profile; gleichnamige Profile aus der Datei werden darübergelegt
+const BUILTIN_PROFILES: &str = r#"
+[dev]
+storage.max_bytes = 1_073_741_824
+evolution.population.size = 4
+learning.interval_secs = 60
+
+[long-run]
+evolution.cycle_interval_secs = 3600
+learning.interval_secs = 1800
+learning.min_request_interval_secs = 10
+
+[offline]
+learning.offline = true
+"#;
+
+/// Gesamte Konfiguration von Kern und Oberfläche
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EvoliConfig {
+    pub profile: Option<String>, // Angewendetes Profil
+    pub paths: PathsConfig,
+    pub storage: StorageConfig,
+    pub evolution: EvolutionConfig,
+    pub learning: LearningConfig,
+    pub interface: InterfaceConfig,
+}
+
+/// Dateien und Verzeichnisse. Datenverzeichnisse sind relativ zu `data_root`,
+/// damit mehrere Instanzen getrennte Wurzeln verwenden können.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathsConfig {
+    pub project_dir: PathBuf,   // Cargo-Projekt des Kerns (Vorlage der Build-Sandbox)
+    pub source: PathBuf,        // Quellcode des Kerns, relativ zu `project_dir`
+    pub data_root: PathBuf,
+    pub knowledge_dir: PathBuf,
+    pub cache_dir: PathBuf,
+    pub log_dir: PathBuf,
+    pub evolved_dir: PathBuf,   // Selbst erzeugte Module
+    pub state_dir: PathBuf,     // Checkpoint, Genomspeicher, Archive
+}
+
+/// Speicherlimit und Schwellen der Bereinigung als Anteil von `max_bytes`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageConfig {
+    pub max_bytes: u64,
+    pub cleanup_threshold: f64, // Oberhalb wird bereinigt
+    pub cleanup_target: f64,    // Bis hierhin wird gelöscht
+    pub quotas: BTreeMap<String, u64>, // Höchstbelegung je löschbarer Kategorie (`cache`, `knowledge`)
+    pub pinned: Vec<String>,    // Muster (`*`, `?`) für Dateien, die nie gelöscht werden
+    pub dry_run: bool,          // Bereinigung nur protokollieren
+}
+
+/// Zyklen und Wahrscheinlichkeiten der Evolution
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EvolutionConfig {
+    pub cycle_interval_secs: u64,
+    pub point_mutation_rate: f64,        // `BasicMutation` verändert eine Zeile
+    pub knowledge_integration_rate: f64, // Gelernte Funktion wird eingefügt
+    pub new_module_rate: f64,
+    pub new_strategy_rate: f64,
+    pub max_mutation_strategies: usize,
+    pub seed: Option<u64>,               // Fester Seed für reproduzierbare Läufe; sonst zufällig
+    pub fitness_weights: BTreeMap<String, f64>, // Gewicht je Fitness-Evaluator (fehlend = 1.0)
+    pub population: PopulationConfig,
+}
+
+/// Lernen aus dem Internet
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LearningConfig {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    pub min_request_interval_secs: u64, // Mindestabstand zweier Anfragen (Sicherheitsregel)
+    pub urls: Vec<String>,              // Startseiten des Crawlers
+    pub allowed_domains: Vec<String>,   // Leer = alle; sonst nur diese Domains und ihre Subdomains
+    pub transport: String,              // "http", "replay" (nur aus `fixture_dir`) oder "record" (HTTP, aufgezeichnet)
+    pub fixture_dir: PathBuf,           // Aufgezeichnete Antworten, relativ zu `paths.data_root`
+    pub offline: bool,                  // Nur aus dem Cache lernen, keine Netzwerkzugriffe
+    pub crawl: CrawlConfig,
+}
+
+/// Einstellungen der Oberfläche `enhanced_evoli_ki`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InterfaceConfig {
+    pub metrics_addr: String, // Leer = kein `/metrics`-Endpunkt
+    pub autonomy_level: u8,
+    pub max_autonomy_level: u8,
+}
+
+impl Default for PathsConfig {
+    fn default() -> Self {
+        PathsConfig {
+            project_dir: PathBuf::from("."),
+            source: PathBuf::from("src/Evoli_Kern.rs"),
+            data_root: PathBuf::from("."),
+            knowledge_dir: PathBuf::from("evoli_knowledge"),
+            cache_dir: PathBuf::from("evoli_cache"),
+            log_dir: PathBuf::from("evoli_logs"),
+            evolved_dir: PathBuf::from("evolved"),
+            state_dir: PathBuf::from("evoli_state"),
+        }
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        StorageConfig {
+            max_bytes: MAX_STORAGE_BYTES,
+            cleanup_threshold: 0.8,
+            cleanup_target: 0.5,
+            quotas: BTreeMap::new(),
+            pinned: Vec::new(),
+            dry_run: false,
+        }
+    }
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        EvolutionConfig {
+            cycle_interval_secs: 10,
+            point_mutation_rate: 0.3,
+            knowledge_integration_rate: 0.3,
+            new_module_rate: 0.1,
+            new_strategy_rate: 0.05,
+            max_mutation_strategies: 10,
+            seed: None,
+            fitness_weights: BTreeMap::new(),
+            population: PopulationConfig::default(),
+        }
+    }
+}
+
+impl Default for LearningConfig {
+    fn default() -> Self {
+        LearningConfig {
+            enabled: true,
+            interval_secs: 10,
+            min_request_interval_secs: 1,
+            urls: vec![
+                "https://doc.rust-lang.org/book/".to_string(),
+                "https://en.wikipedia.org/wiki/Genetic_algorithm".to_string(),
+                "https://en.wikipedia.org/wiki/Self-modifying_code".to_string(),
+            ],
+            allowed_domains: Vec::new(),
+            transport: "http".to_string(),
+            fixture_dir: PathBuf::from("evoli_fixtures"),
+            offline: false,
+            crawl: CrawlConfig::default(),
+        }
+    }
+}
+
+impl Default for InterfaceConfig {
+    fn default() -> Self {
+        InterfaceConfig {
+            metrics_addr: "127.0.0.1:9898".to_string(),
+            autonomy_level: 5,
+            max_autonomy_level: 10,
+        }
+    }
+}
+
+impl PathsConfig {
+    /// `dir` unterhalb von `data_root` (absolute Pfade bleiben unverändert)
+    pub fn data_path(&self, dir: &Path) -> PathBuf {
+        if self.data_root == Path::new(".") {
+            dir.to_path_buf()
+        } else {
+            self.data_root.join(dir)
+        }
+    }
+
+    pub fn source_path(&self) -> PathBuf {
+        if self.project_dir == Path::new(".") {
+            self.source.clone()
+        } else {
+            self.project_dir.join(&self.source)
+        }
+    }
+
+    pub fn knowledge_path(&self) -> PathBuf {
+        self.data_path(&self.knowledge_dir)
+    }
+
+    pub fn cache_path(&self) -> PathBuf {
+        self.data_path(&self.cache_dir)
+    }
+
+    pub fn log_path(&self) -> PathBuf {
+        self.data_path(&self.log_dir)
+    }
+
+    pub fn evolved_path(&self) -> PathBuf {
+        self.data_path(&self.evolved_dir)
+    }
+
+    pub fn state_path(&self) -> PathBuf {
+        self.data_path(&self.state_dir)
+    }
+
+    /// Kommunikationslog der Oberfläche
+    pub fn communication_log(&self) -> PathBuf {
+        self.log_path().join("kommunikation.txt")
+    }
+}
+
+/// Eine geänderte Einstellung, z.B. `storage.max_bytes: 1000 → 2000`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigChange {
+    pub key: String,
+    pub old: String, // Als TOML, leer wenn nicht gesetzt
+    pub new: String,
+}
+
+impl ConfigChange {
+    /// Wird die Änderung erst nach einem Neustart wirksam?
+    pub fn requires_restart(&self) -> bool {
+        RESTART_REQUIRED.iter().any(|prefix| self.key.starts_with(prefix))
+    }
+}
+
+impl std::fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} → {}", self.key, self.old, self.new)
+    }
+}
+
+impl StorageConfig {
+    pub fn cleanup_threshold_bytes(&self) -> u64 {
+        (self.max_bytes as f64 * self.cleanup_threshold) as u64
+    }
+
+    pub fn cleanup_target_bytes(&self) -> u64 {
+        (self.max_bytes as f64 * self.cleanup_target) as u64
+    }
+}
+
+impl EvoliConfig {
+    /// Lädt die Konfiguration mit den Umgebungsvariablen des Prozesses
+    pub fn load(path: Option<&Path>, profile: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load_with_env(path, profile, std::env::vars())
+    }
+
+    /// Lädt Datei und Profil, wendet die `EVOLI_*`-Variablen aus `env` an und validiert
+    /// das Ergebnis. Ohne `path` wird `EVOLI_CONFIG` bzw. `evoli.toml` verwendet,
+    /// ohne `profile` `EVOLI_PROFILE` bzw. der Schlüssel `profile` der Datei.
+    pub fn load_with_env<I>(path: Option<&Path>, profile: Option<&str>, env: I) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let env: Vec<(String, String)> = env.into_iter().collect();
+        let env_value = |name: &str| env.iter().find(|(key, _)| key == name).map(|(_, value)| value.clone());
+
+        // 1. + 2. Datei
+        let path = path.map(Path::to_path_buf).or_else(|| env_value("EVOLI_CONFIG").map(PathBuf::from));
+        let mut sources = Vec::new();
+        let mut table = match &path {
+            Some(path) => read_table(path)?,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => read_table(Path::new(DEFAULT_CONFIG_FILE))?,
+            None => Table::new(),
+        };
+        if let Some(path) = &path {
+            sources.push(path.display().to_string());
+        } else if !table.is_empty() {
+            sources.push(DEFAULT_CONFIG_FILE.to_string());
+        }
+
+        // 3. Profil
+        let mut profiles: Table = BUILTIN_PROFILES.parse()?;
+        if let Some(Value::Table(own)) = table.remove("profiles") {
+            merge(&mut profiles, own);
+        }
+        let profile = profile.map(str::to_string)
+            .or_else(|| env_value("EVOLI_PROFILE"))
+            .or_else(|| table.get("profile").and_then(Value::as_str).map(str::to_string));
+        if let Some(name) = &profile {
+            match profiles.remove(name) {
+                Some(Value::Table(overlay)) => merge(&mut table, overlay),
+                Some(_) => return Err(format!("Profil `{}` muss eine Tabelle sein", name).into()),
+                None => {
+                    let available: Vec<&str> = profiles.keys().map(String::as_str).collect();
+                    return Err(format!("Unbekanntes Profil `{}` (verfügbar: {})", name, available.join(", ")).into());
+                },
+            }
+            table.insert("profile".to_string(), Value::String(name.clone()));
+            sources.push(format!("Profil {}", name));
+        }
+
+        // 4. Umgebungsvariablen
+        for (key, raw) in &env {
+            let Some(rest) = key.strip_prefix("EVOLI_") else { continue };
+            if !rest.contains("__") {
+                continue;
+            }
+            let keys: Vec<String> = rest.split("__").map(str::to_lowercase).collect();
+            set_path(&mut table, &keys, parse_env_value(raw))
+                .map_err(|e| format!("{}: {}", key, e))?;
+            sources.push(key.clone());
+        }
+
+        let origin = if sources.is_empty() { "Standardwerte".to_string() } else { sources.join(", ") };
+        let config: EvoliConfig = Value::Table(table)
+            .try_into()
+            .map_err(|e| format!("Konfiguration ungültig ({}): {}", origin, e))?;
+        config.validate().map_err(|e| format!("Konfiguration ungültig ({}): {}", origin, e))?;
+        Ok(config)
+    }
+
+    /// Prüft Wertebereiche und Zusammenhänge; meldet alle Probleme auf einmal
+    pub fn validate(&self) -> Result<(), String> {
+        let mut problems = Vec::new();
+        let mut rate = |name: &str, value: f64| {
+            if !(0.0..=1.0).contains(&value) {
+                problems.push(format!("{} = {} liegt nicht zwischen 0 und 1", name, value));
+            }
+        };
+        rate("storage.cleanup_threshold", self.storage.cleanup_threshold);
+        rate("storage.cleanup_target", self.storage.cleanup_target);
+        rate("evolution.point_mutation_rate", self.evolution.point_mutation_rate);
+        rate("evolution.knowledge_integration_rate", self.evolution.knowledge_integration_rate);
+        rate("evolution.new_module_rate", self.evolution.new_module_rate);
+        rate("evolution.new_strategy_rate", self.evolution.new_strategy_rate);
+        rate("evolution.population.crossover_rate", self.evolution.population.crossover_rate);
+
+        if self.storage.max_bytes == 0 {
+            problems.push("storage.max_bytes muss größer als 0 sein".to_string());
+        }
+        if self.storage.cleanup_target >= self.storage.cleanup_threshold {
+            problems.push(format!(
+                "storage.cleanup_target ({}) muss kleiner als storage.cleanup_threshold ({}) sein",
+                self.storage.cleanup_target, self.storage.cleanup_threshold
+            ));
+        }
+        let evictable: Vec<&str> = EVICTABLE_CATEGORIES.iter().map(|category| category.as_str()).collect();
+        for name in self.storage.quotas.keys().filter(|name| !evictable.contains(&name.as_str())) {
+            problems.push(format!(
+                "storage.quotas.{}: Quoten gibt es nur für {}",
+                name, evictable.join(", ")
+            ));
+        }
+        let evaluators: Vec<String> = default_evaluators().iter().map(|evaluator| evaluator.name()).collect();
+        for (name, weight) in &self.evolution.fitness_weights {
+            if !evaluators.contains(name) {
+                problems.push(format!(
+                    "evolution.fitness_weights.{}: unbekannter Evaluator ({})",
+                    name, evaluators.join(", ")
+                ));
+            } else if !weight.is_finite() || *weight < 0.0 {
+                problems.push(format!("evolution.fitness_weights.{} = {} darf nicht negativ sein", name, weight));
+            }
+        }
+        if self.evolution.cycle_interval_secs == 0 {
+            problems.push("evolution.cycle_interval_secs muss größer als 0 sein".to_string());
+        }
+        if self.evolution.population.size == 0 {
+            problems.push("evolution.population.size muss größer als 0 sein".to_string());
+        }
+        if self.evolution.population.elitism > self.evolution.population.size {
+            problems.push(format!(
+                "evolution.population.elitism ({}) übersteigt evolution.population.size ({})",
+                self.evolution.population.elitism, self.evolution.population.size
+            ));
+        }
+        if self.evolution.population.replacement == ReplacementStrategy::Generational && self.evolution.population.elitism == 0 {
+            problems.push("evolution.population.elitism muss bei Generational-Ersetzung mindestens 1 sein".to_string());
+        }
+        if self.learning.interval_secs == 0 {
+            problems.push("learning.interval_secs muss größer als 0 sein".to_string());
+        }
+        if self.learning.enabled && !self.learning.offline && self.learning.urls.is_empty() {
+            problems.push("learning.urls ist leer, obwohl learning.enabled gesetzt ist".to_string());
+        }
+        for url in &self.learning.urls {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                problems.push(format!("learning.urls: {} ist keine HTTP(S)-Adresse", url));
+            } else if !domain_allowed(url, &self.learning.allowed_domains) {
+                problems.push(format!("learning.urls: {} liegt außerhalb von learning.allowed_domains", url));
+            }
+        }
+        if self.learning.crawl.max_frontier == 0 {
+            problems.push("learning.crawl.max_frontier muss größer als 0 sein".to_string());
+        }
+        if self.learning.crawl.max_pages_per_host == 0 {
+            problems.push("learning.crawl.max_pages_per_host muss größer als 0 sein".to_string());
+        }
+        if !["http", "replay", "record"].contains(&self.learning.transport.as_str()) {
+            problems.push(format!("learning.transport `{}` ist unbekannt (http, replay, record)", self.learning.transport));
+        }
+        if !self.interface.metrics_addr.is_empty() && self.interface.metrics_addr.parse::<SocketAddr>().is_err() {
+            problems.push(format!("interface.metrics_addr `{}` ist keine Adresse wie 127.0.0.1:9898", self.interface.metrics_addr));
+        }
+        if self.interface.max_autonomy_level > 10 {
+            problems.push(format!("interface.max_autonomy_level ({}) darf höchstens 10 sein", self.interface.max_autonomy_level));
+        }
+        if self.interface.autonomy_level > self.interface.max_autonomy_level {
+            problems.push(format!(
+                "interface.autonomy_level ({}) übersteigt interface.max_autonomy_level ({})",
+                self.interface.autonomy_level, self.interface.max_autonomy_level
+            ));
+        }
+        if self.paths.source.file_name().is_none() {
+            problems.push(format!("paths.source `{}` ist keine Datei", self.paths.source.display()));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems.join("; "))
+        }
+    }
+
+    /// Alle Einstellungen, in denen sich `new` von `self` unterscheidet
+    pub fn changes_to(&self, new: &EvoliConfig) -> Vec<ConfigChange> {
+        let as_table = |config: &EvoliConfig| match Value::try_from(config) {
+            Ok(Value::Table(table)) => table,
+            _ => Table::new(),
+        };
+        let mut changes = Vec::new();
+        diff_tables("", &as_table(self), &as_table(new), &mut changes);
+        changes
+    }
+}
+
+fn diff_tables(prefix: &str, old: &Table, new: &Table, changes: &mut Vec<ConfigChange>) {
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        let path = format!("{}{}", prefix, key);
+        match (old.get(key), new.get(key)) {
+            (Some(Value::Table(old)), Some(Value::Table(new))) => {
+                diff_tables(&format!("{}.", path), old, new, changes);
+            },
+            (old, new) if old != new => changes.push(ConfigChange {
+                key: path,
+                old: old.map(Value::to_string).unwrap_or_default(),
+                new: new.map(Value::to_string).unwrap_or_default(),
+            }),
+            _ => {},
+        }
+    }
+}
+
+fn read_table(path: &Path) -> Result<Table, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Konfiguration {} nicht lesbar: {}", path.display(), e))?;
+    content.parse::<Table>()
+        .map_err(|e| format!("Konfiguration {} ist kein gültiges TOML: {}", path.display(), e).into())
+}
+
+/// Legt `overlay` rekursiv über `base`
+fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(existing)), Value::Table(nested)) => merge(existing, nested),
+            (_, value) => {
+                base.insert(key, value);
+            },
+        }
+    }
+}
+
+fn set_path(table: &mut Table, keys: &[String], value: Value) -> Result<(), String> {
+    let (last, parents) = keys.split_last().ok_or("leerer Schlüssel")?;
+    let mut current = table;
+    for key in parents {
+        current = match current.entry(key.clone()).or_insert_with(|| Value::Table(Table::new())) {
+            Value::Table(nested) => nested,
+            _ => return Err(format!("`{}` ist kein Abschnitt", key)),
+        };
+    }
+    current.insert(last.clone(), value);
+    Ok(())
+}
+
+/// Liest `raw` als TOML-Wert (Zahl, Wahrheitswert, Liste ...), sonst als Text
+fn parse_env_value(raw: &str) -> Value {
+    format!("wert = {}", raw)
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("wert"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_file_profile_and_environment_are_layered() {
+        let path = std::env::temp_dir().join(format!("evoli_config_test_{}.toml", std::process::id()));
+        std::fs::write(&path, r#"
+            profile = "long-run"
+            [paths]
+            data_root = "/srv/evoli/a"
+            [storage]
+            max_bytes = 1000
+            [profiles.long-run.learning]
+            interval_secs = 99
+        "#).unwrap();
+
+        let config = EvoliConfig::load_with_env(Some(&path), None, env(&[
+            ("EVOLI_EVOLUTION__NEW_MODULE_RATE", "0.5"),
+            ("EVOLI_INTERFACE__METRICS_ADDR", "0.0.0.0:9100"),
+            ("EVOLI_UNRELATED", "x"),
+        ])).unwrap();
+        assert_eq!(config.profile.as_deref(), Some("long-run"));
+        assert_eq!(config.paths.knowledge_path(), PathBuf::from("/srv/evoli/a/evoli_knowledge"));
+        assert_eq!(config.storage.max_bytes, 1000);
+        assert_eq!(config.storage.cleanup_threshold_bytes(), 800);
+        assert_eq!(config.evolution.cycle_interval_secs, 3600); // eingebautes Profil
+        assert_eq!(config.learning.interval_secs, 99);           // Profil aus der Datei
+        assert_eq!(config.evolution.new_module_rate, 0.5);
+        assert_eq!(config.interface.metrics_addr, "0.0.0.0:9100");
+
+        let offline = EvoliConfig::load_with_env(Some(&path), None, env(&[("EVOLI_PROFILE", "offline")])).unwrap();
+        assert!(offline.learning.enabled && offline.learning.offline);
+        assert_eq!(offline.evolution.cycle_interval_secs, 10);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_changes_name_keys_and_restart_requirements() {
+        let old = EvoliConfig::default();
+        let mut new = old.clone();
+        new.storage.max_bytes = 2048;
+        new.learning.allowed_domains = vec!["example.org".to_string()];
+        new.paths.data_root = PathBuf::from("/srv/evoli/b");
+
+        let changes = old.changes_to(&new);
+        let keys: Vec<&str> = changes.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(keys, ["learning.allowed_domains", "paths.data_root", "storage.max_bytes"]);
+        assert_eq!(changes[2].to_string(), format!("storage.max_bytes: {} → 2048", MAX_STORAGE_BYTES));
+        assert!(changes[1].requires_restart());
+        assert!(!changes[2].requires_restart());
+        assert!(old.changes_to(&old).is_empty());
+
+        // Lernquellen müssen zu den erlaubten Domains passen
+        assert!(new.validate().unwrap_err().contains("learning.allowed_domains"));
+    }
+
+    #[test]
+    fn test_invalid_values_are_reported_together() {
+        let unknown = EvoliConfig::load_with_env(None, Some("turbo"), env(&[])).unwrap_err();
+        assert!(unknown.to_string().contains("turbo"), "{}", unknown);
+
+        let typo = EvoliConfig::load_with_env(None, None, env(&[("EVOLI_STORAGE__MAX_BYTE", "1")])).unwrap_err();
+        assert!(typo.to_string().contains("max_byte"), "{}", typo);
+
+        let error = EvoliConfig::load_with_env(None, None, env(&[
+            ("EVOLI_STORAGE__CLEANUP_TARGET", "0.9"),
+            ("EVOLI_EVOLUTION__POINT_MUTATION_RATE", "1.5"),
+            ("EVOLI_STORAGE__QUOTAS", "{ cache = 1024, logs = 5 }"),
+            ("EVOLI_LEARNING__TRANSPORT", "\"ftp\""),
+            ("EVOLI_EVOLUTION__POPULATION__ELITISM", "0"),
+            ("EVOLI_EVOLUTION__FITNESS_WEIGHTS", "{ CompileTime = -1.0, Tempo = 2.0, TestPassRatio = 2.0 }"),
+        ])).unwrap_err().to_string();
+        assert!(error.contains("storage.cleanup_target (0.9)"), "{}", error);
+        assert!(error.contains("evolution.point_mutation_rate = 1.5"), "{}", error);
+        assert!(error.contains("storage.quotas.logs"), "{}", error);
+        assert!(!error.contains("storage.quotas.cache"), "{}", error);
+        assert!(error.contains("learning.transport `ftp`"), "{}", error);
+        assert!(error.contains("evolution.population.elitism muss"), "{}", error);
+        assert!(error.contains("evolution.fitness_weights.CompileTime = -1"), "{}", error);
+        assert!(error.contains("evolution.fitness_weights.Tempo: unbekannter Evaluator"), "{}", error);
+        assert!(!error.contains("fitness_weights.TestPassRatio"), "{}", error);
+    }
+}
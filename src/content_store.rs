@@ -0,0 +1,270 @@
+// src/content_store.rs - Komprimierter, deduplizierter Speicher für Cache und Wissensbasis
+//
+// Inhalte werden wie im Genomspeicher unter dem SHA-256 ihres Inhalts gzip-komprimiert
+// abgelegt (`objects/<2 Zeichen>/<Hash>.gz`); identische Inhalte belegen also nur einmal
+// Platz, auch unter verschiedenen Namen. Der Index (`index.json`) ordnet jedem Namen
+// seinen Inhalt und die Metadaten zu (Quelle, Abrufzeit, Content-Type, Originalgröße).
+// Ein Objekt wird gelöscht, sobald kein Eintrag mehr darauf verweist.
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::http_cache::HttpCacheInfo;
+
+/// Ein Eintrag des Index
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContentEntry {
+    pub name: String,
+    pub hash: String,                 // SHA-256 des unkomprimierten Inhalts
+    pub url: Option<String>,          // Herkunft, falls aus dem Internet
+    pub fetched_at: String,           // RFC 3339
+    pub content_type: Option<String>,
+    pub original_size: u64,
+    pub stored_size: u64,             // Komprimiert, einmal je Inhalt
+    #[serde(default)]
+    pub http: Option<HttpCacheInfo>,  // Validatoren und Frische, nur für abgerufene Seiten
+}
+
+impl ContentEntry {
+    /// Abrufzeit in Unix-Sekunden
+    pub fn fetched_timestamp(&self) -> Option<i64> {
+        chrono::DateTime::parse_from_rfc3339(&self.fetched_at).ok().map(|time| time.timestamp())
+    }
+}
+
+/// Ergebnis von `ContentStore::put`
+#[derive(Clone, Debug, PartialEq)]
+pub struct PutOutcome {
+    pub hash: String,
+    pub deduplicated: bool, // Inhalt lag bereits vor, es wurde kein Objekt geschrieben
+}
+
+/// Inhaltsadressierter Speicher unter `root`
+#[derive(Debug)]
+pub struct ContentStore {
+    pub root: PathBuf,
+    pub entries: BTreeMap<String, ContentEntry>,
+}
+
+impl ContentStore {
+    /// Öffnet den Speicher; ein fehlender oder defekter Index ergibt einen leeren Speicher
+    pub fn open(root: &Path) -> Self {
+        let entries = fs::read_to_string(root.join("index.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        ContentStore { root: root.to_path_buf(), entries }
+    }
+
+    pub fn index_path(&self) -> PathBuf {
+        self.root.join("index.json")
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.root.join("objects").join(&hash[..2]).join(format!("{}.gz", hash))
+    }
+
+    /// Logischer Pfad eines Eintrags (für Zugriffsverzeichnis, Muster und Meldungen)
+    pub fn entry_path(&self, name: &str) -> PathBuf {
+        self.root.join(name)
+    }
+
+    /// Erster Eintrag mit diesem Inhalt
+    pub fn find_by_hash(&self, hash: &str) -> Option<&ContentEntry> {
+        self.entries.values().find(|entry| entry.hash == hash)
+    }
+
+    /// Anzahl der Einträge, die auf den Inhalt `hash` verweisen
+    pub fn references(&self, hash: &str) -> usize {
+        self.entries.values().filter(|entry| entry.hash == hash).count()
+    }
+
+    /// Legt `payload` unter `name` ab; ein vorhandener Eintrag gleichen Namens wird ersetzt
+    pub fn put(
+        &mut self,
+        name: &str,
+        payload: &[u8],
+        url: Option<&str>,
+        content_type: Option<&str>,
+    ) -> Result<PutOutcome, Box<dyn std::error::Error>> {
+        let hash = content_hash(payload);
+        let existing = self.find_by_hash(&hash).map(|entry| entry.stored_size);
+        let deduplicated = existing.is_some();
+        let stored_size = match existing {
+            Some(size) => size,
+            None => self.write_object(&hash, payload)?,
+        };
+
+        let entry = ContentEntry {
+            name: name.to_string(),
+            hash: hash.clone(),
+            url: url.map(str::to_string),
+            fetched_at: chrono::Local::now().to_rfc3339(),
+            content_type: content_type.map(str::to_string),
+            original_size: payload.len() as u64,
+            stored_size,
+            http: None,
+        };
+        if let Some(previous) = self.entries.insert(name.to_string(), entry) {
+            if previous.hash != hash {
+                self.release(&previous.hash)?;
+            }
+        }
+
+        self.save()?;
+        Ok(PutOutcome { hash, deduplicated })
+    }
+
+    /// Setzt die HTTP-Cacheangaben eines Eintrags und vermerkt den Zeitpunkt als letzte
+    /// Bestätigung durch den Server (`fetched_at`)
+    pub fn set_http(&mut self, name: &str, http: HttpCacheInfo) -> Result<(), Box<dyn std::error::Error>> {
+        let entry = self.entries.get_mut(name).ok_or_else(|| format!("{} ist nicht im Speicher {}", name, self.root.display()))?;
+        entry.http = Some(http);
+        entry.fetched_at = chrono::Local::now().to_rfc3339();
+        self.save()
+    }
+
+    /// Unkomprimierter Inhalt eines Eintrags; ein beschädigtes Objekt ergibt einen Fehler
+    pub fn get(&self, name: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let entry = self.entries.get(name).ok_or_else(|| format!("{} ist nicht im Speicher {}", name, self.root.display()))?;
+        let mut payload = Vec::new();
+        GzDecoder::new(fs::File::open(self.object_path(&entry.hash))?).read_to_end(&mut payload)?;
+        if content_hash(&payload) != entry.hash {
+            return Err(format!("Inhalt von {} ist beschädigt (Hash stimmt nicht)", name).into());
+        }
+        Ok(payload)
+    }
+
+    pub fn get_string(&self, name: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(String::from_utf8(self.get(name)?)?)
+    }
+
+    /// Entfernt einen Eintrag; liefert die freigegebenen Bytes (0, solange andere Einträge
+    /// denselben Inhalt nutzen)
+    pub fn remove(&mut self, name: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        let Some(entry) = self.entries.remove(name) else { return Ok(0) };
+        let freed = self.release(&entry.hash)?;
+        self.save()?;
+        Ok(freed)
+    }
+
+    /// Übernimmt lose Dateien direkt unter `root` (ältere Ablage) in den Speicher
+    pub fn import_loose_files(&mut self) -> Result<usize, Box<dyn std::error::Error>> {
+        if !self.root.is_dir() {
+            return Ok(0);
+        }
+        let mut imported = 0;
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            if !path.is_file() || name.starts_with("index.json") {
+                continue;
+            }
+            self.put(&name, &fs::read(&path)?, None, None)?;
+            fs::remove_file(&path)?;
+            imported += 1;
+        }
+        Ok(imported)
+    }
+
+    /// Schreibt den Index atomar
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(&self.root)?;
+        let temporary = self.index_path().with_extension("json.tmp");
+        fs::write(&temporary, serde_json::to_string(&self.entries)?)?;
+        fs::rename(&temporary, self.index_path())?;
+        Ok(())
+    }
+
+    fn write_object(&self, hash: &str, payload: &[u8]) -> Result<u64, Box<dyn std::error::Error>> {
+        let path = self.object_path(hash);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut encoder = GzEncoder::new(fs::File::create(&path)?, Compression::default());
+        encoder.write_all(payload)?;
+        encoder.finish()?;
+        Ok(fs::metadata(&path)?.len())
+    }
+
+    /// Löscht das Objekt, wenn kein Eintrag mehr darauf verweist
+    fn release(&self, hash: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        let path = self.object_path(hash);
+        if self.references(hash) > 0 || !path.exists() {
+            return Ok(0);
+        }
+        let size = fs::metadata(&path)?.len();
+        fs::remove_file(&path)?;
+        Ok(size)
+    }
+}
+
+/// Hex-kodierter SHA-256 eines Inhalts (für Text identisch mit `genome_id`)
+pub fn content_hash(payload: &[u8]) -> String {
+    Sha256::digest(payload).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(name: &str) -> ContentStore {
+        let root = std::env::temp_dir().join(format!("evoli_content_{}_{}", name, std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        ContentStore::open(&root)
+    }
+
+    #[test]
+    fn test_deduplicates_compresses_and_releases_objects() {
+        let mut store = temp_store("dedup");
+        let page = "<html>".repeat(1000);
+
+        let first = store.put("a.html", page.as_bytes(), Some("https://example.org/"), Some("text/html")).unwrap();
+        let second = store.put("b.html", page.as_bytes(), None, None).unwrap();
+        assert!(!first.deduplicated && second.deduplicated);
+        assert_eq!(first.hash, second.hash);
+
+        let entry = &store.entries["a.html"];
+        assert_eq!(entry.original_size, 6000);
+        assert!(entry.stored_size < 200);
+        assert_eq!(entry.url.as_deref(), Some("https://example.org/"));
+        assert!(entry.fetched_timestamp().is_some());
+
+        // Wiedereröffnen liest den Index, der Inhalt bleibt lesbar
+        let mut store = ContentStore::open(&store.root);
+        assert_eq!(store.get_string("b.html").unwrap(), page);
+
+        // Das Objekt verschwindet erst mit dem letzten Verweis
+        assert_eq!(store.remove("a.html").unwrap(), 0);
+        assert!(store.remove("b.html").unwrap() > 0);
+        assert!(!store.object_path(&first.hash).exists());
+
+        fs::remove_dir_all(&store.root).ok();
+    }
+
+    #[test]
+    fn test_imports_loose_files_and_replaces_by_name() {
+        let mut store = temp_store("import");
+        fs::create_dir_all(&store.root).unwrap();
+        fs::write(store.root.join("evoli_knowledge_1.rs"), "fn alt() {}\n").unwrap();
+
+        assert_eq!(store.import_loose_files().unwrap(), 1);
+        assert!(!store.root.join("evoli_knowledge_1.rs").exists());
+        assert_eq!(store.get_string("evoli_knowledge_1.rs").unwrap(), "fn alt() {}\n");
+
+        // Gleicher Name, neuer Inhalt: das alte Objekt wird freigegeben
+        let old_hash = store.entries["evoli_knowledge_1.rs"].hash.clone();
+        store.put("evoli_knowledge_1.rs", b"fn neu() {}\n", None, None).unwrap();
+        assert!(!store.object_path(&old_hash).exists());
+        assert_eq!(store.entries.len(), 1);
+
+        fs::remove_dir_all(&store.root).ok();
+    }
+}
@@ -0,0 +1,435 @@
+// src/crawler.rs - Crawl-Frontier für das Internet-Lernen
+//
+// Statt einer festen URL-Liste arbeitet das Lernen eine persistente Warteschlange ab,
+// die aus `learning.urls` gestartet wird und um die Links der abgerufenen Seiten
+// wächst (Breitensuche bis `max_depth`). Jede URL wird je Runde nur einmal besucht.
+// Vor dem ersten Abruf eines Hosts wird dessen robots.txt gelesen; zwischen zwei
+// Abrufen desselben Hosts liegt mindestens `host_delay_secs` bzw. das `Crawl-delay`
+// aus robots.txt. Ist die Warteschlange leer, beginnt eine neue Runde ab den Startseiten.
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fs;
+use std::path::Path;
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::safety::domain_allowed;
+
+/// Produkt-Token, unter dem robots.txt-Gruppen gesucht werden
+pub const CRAWLER_AGENT: &str = "EvoliKI";
+
+/// Gültigkeit einer gelesenen robots.txt bzw. eines fehlgeschlagenen Abrufs
+pub const ROBOTS_TTL_SECS: i64 = 24 * 3600;
+pub const ROBOTS_RETRY_SECS: i64 = 3600;
+
+/// Grenzen des Crawlers (`learning.crawl`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CrawlConfig {
+    pub max_depth: u32,           // Startseiten haben Tiefe 0
+    pub max_frontier: usize,      // Höchstens so viele wartende URLs
+    pub max_pages_per_host: u64,  // Je Runde
+    pub follow_external: bool,    // Auch Links auf Hosts außerhalb der Startseiten folgen
+    pub host_delay_secs: u64,     // Mindestabstand zweier Abrufe desselben Hosts
+    pub respect_robots: bool,
+}
+
+impl Default for CrawlConfig {
+    fn default() -> Self {
+        CrawlConfig {
+            max_depth: 2,
+            max_frontier: 5000,
+            max_pages_per_host: 100,
+            follow_external: false,
+            host_delay_secs: 5,
+            respect_robots: true,
+        }
+    }
+}
+
+/// Eine wartende URL
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FrontierEntry {
+    pub url: String,
+    pub depth: u32,
+}
+
+/// Regeln einer robots.txt für `CRAWLER_AGENT`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RobotsRules {
+    pub rules: Vec<(bool, String)>, // (erlaubt, Muster); `*` und `$` wie in RFC 9309
+    pub crawl_delay: Option<f64>,   // Sekunden
+}
+
+impl RobotsRules {
+    pub fn allow_all() -> Self {
+        RobotsRules::default()
+    }
+
+    pub fn disallow_all() -> Self {
+        RobotsRules { rules: vec![(false, "/".to_string())], crawl_delay: None }
+    }
+
+    /// Liest die Gruppe für `agent`; ohne eigene Gruppe gilt die für `*`
+    pub fn parse(content: &str, agent: &str) -> Self {
+        let agent = agent.to_lowercase();
+        let (mut specific, mut wildcard) = (RobotsRules::default(), RobotsRules::default());
+        let mut specific_seen = false;
+        let mut group: Vec<String> = Vec::new();
+        let mut in_rules = false;
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else { continue };
+            let (key, value) = (key.trim().to_lowercase(), value.trim());
+
+            if key == "user-agent" {
+                if in_rules {
+                    group.clear();
+                    in_rules = false;
+                }
+                let name = value.to_lowercase();
+                specific_seen |= name == agent;
+                group.push(name);
+                continue;
+            }
+            in_rules = true;
+
+            let mut targets = Vec::new();
+            if group.contains(&agent) {
+                targets.push(&mut specific);
+            }
+            if group.iter().any(|name| name == "*") {
+                targets.push(&mut wildcard);
+            }
+            for rules in targets {
+                match key.as_str() {
+                    "allow" if !value.is_empty() => rules.rules.push((true, value.to_string())),
+                    "disallow" if !value.is_empty() => rules.rules.push((false, value.to_string())),
+                    "crawl-delay" => rules.crawl_delay = value.parse().ok(),
+                    _ => {},
+                }
+            }
+        }
+
+        if specific_seen { specific } else { wildcard }
+    }
+
+    /// Längstes passendes Muster entscheidet, bei Gleichstand `Allow`
+    pub fn allows(&self, url: &Url) -> bool {
+        let mut path = url.path().to_string();
+        if let Some(query) = url.query() {
+            path = format!("{}?{}", path, query);
+        }
+        if path == "/robots.txt" {
+            return true;
+        }
+        self.rules.iter()
+            .filter(|(_, pattern)| robots_match(pattern, &path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .is_none_or(|(allow, _)| *allow)
+    }
+}
+
+/// Zustand eines Hosts (Ursprung `schema://host:port`)
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HostState {
+    pub robots: Option<RobotsRules>,
+    pub robots_expires_at: i64, // Unix-Sekunden
+    pub last_request_ms: i64,   // Unix-Millisekunden, 0 = nie
+    pub pages: u64,             // Abrufe in dieser Runde
+}
+
+/// Persistente Warteschlange des Crawlers
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CrawlFrontier {
+    pub queue: VecDeque<FrontierEntry>,
+    pub visited: BTreeSet<String>,
+    pub hosts: BTreeMap<String, HostState>,
+    pub seed_hosts: BTreeSet<String>,
+    pub rounds: u64,
+}
+
+impl CrawlFrontier {
+    /// Lädt die Frontier; fehlt sie oder ist sie unlesbar, beginnt sie leer
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Reiht noch nicht besuchte Startseiten ein; liefert die Anzahl neuer Einträge
+    pub fn seed(&mut self, urls: &[String]) -> usize {
+        let mut added = 0;
+        for url in urls.iter().filter_map(|url| normalize_url(url)) {
+            self.seed_hosts.insert(host_key(&url));
+            if !self.visited.contains(url.as_str()) && !self.is_queued(url.as_str()) {
+                self.queue.push_back(FrontierEntry { url: url.to_string(), depth: 0 });
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Neue Runde: Besuche und Seitenzähler vergessen, ab den Startseiten neu beginnen
+    pub fn begin_round(&mut self, urls: &[String]) -> usize {
+        self.visited.clear();
+        for host in self.hosts.values_mut() {
+            host.pages = 0;
+        }
+        self.rounds += 1;
+        self.seed(urls)
+    }
+
+    pub fn is_queued(&self, url: &str) -> bool {
+        self.queue.iter().any(|entry| entry.url == url)
+    }
+
+    /// Abstand zwischen zwei Abrufen von `host` in Millisekunden
+    pub fn host_delay_ms(&self, host: &str, config: &CrawlConfig) -> i64 {
+        let crawl_delay = self.hosts.get(host)
+            .and_then(|state| state.robots.as_ref())
+            .and_then(|robots| robots.crawl_delay)
+            .unwrap_or(0.0);
+        ((config.host_delay_secs as f64).max(crawl_delay) * 1000.0) as i64
+    }
+
+    /// Entnimmt die erste URL, deren Host wieder abgerufen werden darf. URLs von Hosts,
+    /// die ihr Seitenlimit erreicht haben, werden verworfen.
+    pub fn next_ready(&mut self, now_ms: i64, config: &CrawlConfig) -> Option<FrontierEntry> {
+        let hosts = &self.hosts;
+        self.queue.retain(|entry| {
+            hosts.get(&host_of(&entry.url)).is_none_or(|state| state.pages < config.max_pages_per_host)
+        });
+
+        let position = self.queue.iter().position(|entry| {
+            let host = host_of(&entry.url);
+            let last = self.hosts.get(&host).map_or(0, |state| state.last_request_ms);
+            last == 0 || now_ms - last >= self.host_delay_ms(&host, config)
+        })?;
+        self.queue.remove(position)
+    }
+
+    /// Stellt eine entnommene URL wieder an den Anfang (Abruf wurde nicht durchgeführt)
+    pub fn requeue(&mut self, entry: FrontierEntry) {
+        self.queue.push_front(entry);
+    }
+
+    /// Muss die robots.txt des Hosts von `url` (erneut) gelesen werden?
+    pub fn needs_robots(&self, url: &str, now: i64) -> bool {
+        self.hosts.get(&host_of(url)).is_none_or(|state| state.robots.is_none() || state.robots_expires_at <= now)
+    }
+
+    pub fn set_robots(&mut self, url: &str, robots: RobotsRules, expires_at: i64) {
+        let state = self.hosts.entry(host_of(url)).or_default();
+        state.robots = Some(robots);
+        state.robots_expires_at = expires_at;
+    }
+
+    /// Erlaubt die gespeicherte robots.txt den Abruf? Ohne Regeln ja.
+    pub fn robots_allow(&self, url: &str) -> bool {
+        let Ok(parsed) = Url::parse(url) else { return false };
+        self.hosts.get(&host_of(url))
+            .and_then(|state| state.robots.as_ref())
+            .is_none_or(|robots| robots.allows(&parsed))
+    }
+
+    /// Entfernt wartende URLs außerhalb von `allowed_domains` (leer = alle erlaubt), etwa nach
+    /// einer Einschränkung der Domains; liefert die Anzahl entfernter Einträge
+    pub fn retain_domains(&mut self, allowed_domains: &[String]) -> usize {
+        let before = self.queue.len();
+        self.queue.retain(|entry| domain_allowed(&entry.url, allowed_domains));
+        before - self.queue.len()
+    }
+
+    /// Besucht, ohne abgerufen zu werden (z.B. durch robots.txt gesperrt)
+    pub fn mark_visited(&mut self, url: &str) {
+        self.visited.insert(url.to_string());
+    }
+
+    /// Vermerkt den Abruf der Seite `url` zum Zeitpunkt `now_ms`
+    pub fn record_fetch(&mut self, url: &str, now_ms: i64) {
+        self.visited.insert(url.to_string());
+        self.record_request(url, now_ms);
+        self.hosts.entry(host_of(url)).or_default().pages += 1;
+    }
+
+    /// Vermerkt eine Anfrage an den Host von `url`, die keine Seite ist (z.B. robots.txt);
+    /// sie zählt nur für den Abstand zum nächsten Abruf
+    pub fn record_request(&mut self, url: &str, now_ms: i64) {
+        self.hosts.entry(host_of(url)).or_default().last_request_ms = now_ms;
+    }
+
+    /// Nimmt die Links einer Seite der Tiefe `parent.depth` auf; liefert die Anzahl neuer Einträge
+    pub fn discover(&mut self, parent: &FrontierEntry, links: &[String], config: &CrawlConfig, allowed_domains: &[String]) -> usize {
+        if parent.depth >= config.max_depth {
+            return 0;
+        }
+        let mut added = 0;
+        for link in links {
+            if self.queue.len() >= config.max_frontier {
+                break;
+            }
+            let Some(url) = normalize_url(link) else { continue };
+            let url_string = url.to_string();
+            if self.visited.contains(&url_string)
+                || self.is_queued(&url_string)
+                || !domain_allowed(&url_string, allowed_domains)
+                || (!config.follow_external && !self.seed_hosts.contains(&host_key(&url)))
+            {
+                continue;
+            }
+            self.queue.push_back(FrontierEntry { url: url_string, depth: parent.depth + 1 });
+            added += 1;
+        }
+        added
+    }
+}
+
+/// HTTP(S)-URL ohne Fragment; andere Schemata und ungültige URLs ergeben `None`
+pub fn normalize_url(url: &str) -> Option<Url> {
+    let mut url = Url::parse(url).ok()?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return None;
+    }
+    url.set_fragment(None);
+    Some(url)
+}
+
+/// Ursprung einer URL (`schema://host:port`), Schlüssel der Host-Zustände
+pub fn host_of(url: &str) -> String {
+    Url::parse(url).map(|url| host_key(&url)).unwrap_or_default()
+}
+
+fn host_key(url: &Url) -> String {
+    url.origin().ascii_serialization()
+}
+
+/// Adresse der robots.txt zum Host von `url`
+pub fn robots_url(url: &str) -> String {
+    format!("{}/robots.txt", host_of(url))
+}
+
+/// Ziele aller `<a href>` einer HTML-Seite, aufgelöst gegen `base`, ohne Duplikate
+pub fn extract_links(html: &str, base: &str) -> Vec<String> {
+    let Ok(base) = Url::parse(base) else { return Vec::new() };
+    let lower = html.to_ascii_lowercase();
+    let mut links = Vec::new();
+    let mut position = 0;
+
+    while let Some(offset) = lower[position..].find("<a") {
+        let start = position + offset;
+        position = start + 2;
+        if !lower[position..].starts_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+        let end = lower[start..].find('>').map_or(lower.len(), |end| start + end);
+        let Some(href) = lower[start..end].find("href") else { continue };
+
+        let rest = html[start + href + 4..end].trim_start();
+        let Some(rest) = rest.strip_prefix('=') else { continue };
+        let rest = rest.trim_start();
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => rest[1..].split(quote).next().unwrap_or_default(),
+            _ => rest.split(|c: char| c.is_ascii_whitespace()).next().unwrap_or_default(),
+        };
+
+        let value = value.trim().replace("&amp;", "&");
+        if let Some(url) = base.join(&value).ok().and_then(|url| normalize_url(url.as_str())) {
+            let url = url.to_string();
+            if !links.contains(&url) {
+                links.push(url);
+            }
+        }
+    }
+    links
+}
+
+/// Muster aus robots.txt: Präfix mit `*` als Platzhalter, `$` verankert am Ende
+fn robots_match(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let Some(rest) = path.strip_prefix(parts[0]) else { return false };
+
+    let mut rest = rest;
+    for (i, part) in parts.iter().enumerate().skip(1) {
+        let is_last = i == parts.len() - 1;
+        if is_last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(found) => rest = &rest[found + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_robots_groups_and_longest_match() {
+        let robots = RobotsRules::parse(
+            "User-agent: *\nDisallow: /\n\n\
+             User-agent: evoliki\nUser-agent: andere\nDisallow: /privat # Kommentar\nAllow: /privat/offen\n\
+             Disallow: /*.pdf$\nCrawl-delay: 7\n",
+            CRAWLER_AGENT,
+        );
+        let allows = |path: &str| robots.allows(&Url::parse(&format!("https://example.org{}", path)).unwrap());
+
+        assert_eq!(robots.crawl_delay, Some(7.0));
+        assert!(allows("/buch/kapitel1.html"));
+        assert!(!allows("/privat/geheim"));
+        assert!(allows("/privat/offen/seite"));
+        assert!(!allows("/docs/handbuch.pdf"));
+        assert!(allows("/docs/handbuch.pdf?seite=2"));
+        assert!(allows("/robots.txt"));
+
+        // Ohne eigene Gruppe gilt `*`
+        let other = RobotsRules::parse("User-agent: *\nDisallow: /\n", "Fremd");
+        assert!(!other.allows(&Url::parse("https://example.org/a").unwrap()));
+        assert!(RobotsRules::parse("", CRAWLER_AGENT).allows(&Url::parse("https://example.org/a").unwrap()));
+    }
+
+    #[test]
+    fn test_frontier_discovers_links_with_limits_and_politeness() {
+        let html = r#"<p><a href="/kapitel2.html#abschnitt">2</a> <A HREF='kapitel3.html'>3</A>
+                      <a class="x" href=https://extern.example/seite>x</a> <a href="mailto:a@b.c">m</a>
+                      <a href="/kapitel2.html">doppelt</a> <abbr>kein Link</abbr></p>"#;
+        let links = extract_links(html, "https://example.org/buch/kapitel1.html");
+        assert_eq!(links, [
+            "https://example.org/kapitel2.html",
+            "https://example.org/buch/kapitel3.html",
+            "https://extern.example/seite",
+        ]);
+
+        let config = CrawlConfig { max_depth: 1, host_delay_secs: 10, ..CrawlConfig::default() };
+        let mut frontier = CrawlFrontier::default();
+        assert_eq!(frontier.seed(&["https://example.org/buch/kapitel1.html#oben".to_string()]), 1);
+
+        let start = frontier.next_ready(1_000, &config).unwrap();
+        frontier.record_fetch(&start.url, 1_000);
+        assert_eq!(frontier.discover(&start, &links, &config, &[]), 2); // Externer Host ausgelassen
+
+        // Der Host wartet `host_delay_secs`, danach geht es in Reihenfolge weiter
+        assert!(frontier.next_ready(5_000, &config).is_none());
+        let next = frontier.next_ready(11_000, &config).unwrap();
+        assert_eq!((next.url.as_str(), next.depth), ("https://example.org/kapitel2.html", 1));
+
+        // Tiefe 1 ist die Grenze, Besuchtes wird nicht erneut eingereiht
+        assert_eq!(frontier.discover(&next, &links, &config, &[]), 0);
+        assert_eq!(frontier.seed(&["https://example.org/buch/kapitel1.html".to_string()]), 0);
+        assert_eq!(frontier.begin_round(&["https://example.org/buch/kapitel1.html".to_string()]), 1);
+
+        // Eingeschränkte Domains leeren die Warteschlange von fremden Hosts
+        assert_eq!(frontier.retain_domains(&["example.org".to_string()]), 0);
+        assert_eq!(frontier.retain_domains(&["example.com".to_string()]), 2);
+        assert!(frontier.queue.is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+// src/crossover.rs - Rekombination zweier Genome
+//
+// Beide Operatoren arbeiten auf dem Syntaxbaum, übernehmen den Quelltext aber
+// textuell aus dem zweiten Elternteil. Kommentare innerhalb der getauschten
+// Bereiche wandern dadurch mit.
+use std::collections::HashMap;
+use std::ops::Range;
+
+use quote::ToTokens;
+use rand::Rng;
+use syn::visit::{self, Visit};
+use syn::{Block, ImplItem, Item};
+
+use crate::ast_mutation::{has_plain_prefix, item_byte_range, node_byte_range, splice};
+use crate::{CrossoverStrategy, EvoliRng};
+
+/// Tauscht ganze `fn`- und `impl`-Items gleichen Namens zwischen den Eltern aus
+pub struct ItemCrossover;
+
+/// Ein-Punkt-Crossover der Anweisungslisten innerhalb gleichnamiger Funktionen
+pub struct SubtreeCrossover;
+
+impl CrossoverStrategy for ItemCrossover {
+    fn crossover(&self, first: &str, second: &str, rng: &mut EvoliRng) -> String {
+        let result = with_parsed_pair(first, second, |file_a, file_b| {
+            let donor: HashMap<String, Range<usize>> = file_b
+                .items
+                .iter()
+                .filter_map(|item| Some((item_key(item)?, item_byte_range(item)?)))
+                .collect();
+
+            // Items, die in beiden Eltern existieren, sich aber unterscheiden
+            let candidates: Vec<(Range<usize>, Range<usize>)> = file_a
+                .items
+                .iter()
+                .filter_map(|item| {
+                    let own = item_byte_range(item)?;
+                    let other = donor.get(&item_key(item)?)?.clone();
+                    (first[own.clone()] != second[other.clone()]).then_some((own, other))
+                })
+                .collect();
+            if candidates.is_empty() {
+                return None;
+            }
+
+            // Uniformes Crossover; mindestens ein Item wird übernommen
+            let mut chosen: Vec<_> = candidates.iter().filter(|_| rng.gen_bool(0.5)).cloned().collect();
+            if chosen.is_empty() {
+                chosen.push(candidates[rng.gen_range(0..candidates.len())].clone());
+            }
+
+            // Von hinten ersetzen, damit frühere Offsets gültig bleiben
+            chosen.sort_by_key(|(own, _)| std::cmp::Reverse(own.start));
+            let mut child = first.to_string();
+            for (own, other) in chosen {
+                child = splice(&child, own, &second[other]);
+            }
+            Some(child)
+        });
+
+        result.unwrap_or_else(|| first.to_string())
+    }
+
+    fn name(&self) -> String {
+        "ItemCrossover".to_string()
+    }
+}
+
+impl CrossoverStrategy for SubtreeCrossover {
+    fn crossover(&self, first: &str, second: &str, rng: &mut EvoliRng) -> String {
+        let result = with_parsed_pair(first, second, |file_a, file_b| {
+            let bodies_b: HashMap<String, &Block> = function_bodies(file_b).into_iter().collect();
+            let pairs: Vec<(&Block, &Block)> = function_bodies(file_a)
+                .into_iter()
+                .filter_map(|(key, body)| Some((body, *bodies_b.get(&key)?)))
+                .collect();
+            if pairs.is_empty() {
+                return None;
+            }
+
+            let (body_a, body_b) = pairs[rng.gen_range(0..pairs.len())];
+
+            // Bei gleicher Blockstruktur einen beliebigen (verschachtelten) Block wählen
+            let blocks_a = nested_blocks(body_a);
+            let blocks_b = nested_blocks(body_b);
+            let index = if blocks_a.len() == blocks_b.len() { rng.gen_range(0..blocks_a.len()) } else { 0 };
+            let (stmts_a, stmts_b) = (&blocks_a[index].stmts, &blocks_b[index].stmts);
+            if stmts_a.is_empty() || stmts_b.is_empty() {
+                return None;
+            }
+
+            // Schnittpunkte: Kind = a[..i] + b[j..]
+            let i = rng.gen_range(0..stmts_a.len());
+            let j = if stmts_a.len() == stmts_b.len() { i } else { rng.gen_range(0..stmts_b.len()) };
+
+            let own = node_byte_range(&stmts_a[i])?.start..node_byte_range(stmts_a.last()?)?.end;
+            let other = node_byte_range(&stmts_b[j])?.start..node_byte_range(stmts_b.last()?)?.end;
+            Some(splice(first, own, &second[other]))
+        });
+
+        result.unwrap_or_else(|| first.to_string())
+    }
+
+    fn name(&self) -> String {
+        "SubtreeCrossover".to_string()
+    }
+}
+
+/// Alle eingebauten Crossover-Operatoren
+pub fn crossover_operators() -> Vec<Box<dyn CrossoverStrategy>> {
+    vec![Box::new(ItemCrossover), Box::new(SubtreeCrossover)]
+}
+
+/// Parst beide Eltern, führt `f` aus und prüft, dass das Kind gültiges Rust ist
+fn with_parsed_pair<F>(first: &str, second: &str, f: F) -> Option<String>
+where
+    F: FnOnce(&syn::File, &syn::File) -> Option<String>,
+{
+    if !has_plain_prefix(first) || !has_plain_prefix(second) {
+        return None;
+    }
+
+    let result = match (syn::parse_file(first), syn::parse_file(second)) {
+        (Ok(a), Ok(b)) => f(&a, &b).filter(|child| child != first && syn::parse_file(child).is_ok()),
+        _ => None,
+    };
+
+    proc_macro2::extra::invalidate_current_thread_spans();
+    result
+}
+
+/// Name eines austauschbaren Top-Level-Items
+fn item_key(item: &Item) -> Option<String> {
+    match item {
+        Item::Fn(function) => Some(format!("fn {}", function.sig.ident)),
+        Item::Impl(block) => Some(format!("impl {}", impl_name(block))),
+        _ => None,
+    }
+}
+
+fn impl_name(block: &syn::ItemImpl) -> String {
+    let trait_part = block
+        .trait_
+        .as_ref()
+        .map(|(_, path, _)| format!("{} for ", path.to_token_stream()))
+        .unwrap_or_default();
+    format!("{}{}", trait_part, block.self_ty.to_token_stream())
+}
+
+/// Rümpfe aller freien Funktionen und Methoden, benannt nach ihrem Pfad
+fn function_bodies(file: &syn::File) -> Vec<(String, &Block)> {
+    let mut bodies = Vec::new();
+    for item in &file.items {
+        match item {
+            Item::Fn(function) => bodies.push((format!("fn {}", function.sig.ident), function.block.as_ref())),
+            Item::Impl(block) => {
+                let owner = impl_name(block);
+                for member in &block.items {
+                    if let ImplItem::Fn(method) = member {
+                        bodies.push((format!("{}::{}", owner, method.sig.ident), &method.block));
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+    bodies
+}
+
+/// Ein Block und alle darin verschachtelten Blöcke in Quelltextreihenfolge
+fn nested_blocks(body: &Block) -> Vec<&Block> {
+    struct Blocks<'a>(Vec<&'a Block>);
+
+    impl<'a> Visit<'a> for Blocks<'a> {
+        fn visit_block(&mut self, block: &'a Block) {
+            self.0.push(block);
+            visit::visit_block(self, block);
+        }
+    }
+
+    let mut blocks = Blocks(Vec::new());
+    blocks.visit_block(body);
+    blocks.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_item_crossover_takes_function_from_second_parent() {
+        let first = "fn a() -> u8 { 1 }\nfn b() -> u8 { 2 }\n";
+        let second = "fn a() -> u8 { 10 }\nfn c() -> u8 { 3 }\n";
+
+        let child = ItemCrossover.crossover(first, second, &mut EvoliRng::seed_from_u64(1));
+        assert_eq!(child, "fn a() -> u8 { 10 }\nfn b() -> u8 { 2 }\n");
+    }
+
+    #[test]
+    fn test_subtree_crossover_combines_statements() {
+        let first = "impl K {\n    fn f(&self) {\n        eins();\n        zwei();\n    }\n}\n";
+        let second = "impl K {\n    fn f(&self) {\n        drei();\n        vier();\n    }\n}\n";
+
+        let mut rng = EvoliRng::seed_from_u64(1);
+        for _ in 0..20 {
+            let child = SubtreeCrossover.crossover(first, second, &mut rng);
+            assert!(syn::parse_file(&child).is_ok());
+            assert!(child.contains("vier();"));
+        }
+    }
+}
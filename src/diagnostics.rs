@@ -0,0 +1,452 @@
+// src/diagnostics.rs - Strukturierte Compiler-Diagnosen und diagnosegeleitete Reparatur
+//
+// Kandidaten werden mit `--message-format=json` gebaut; die rustc-Diagnosen
+// werden hier in typisierte Datensätze übersetzt, pro Kandidat aufbewahrt und
+// zu Fehlerstatistiken verdichtet.
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BuildSandbox, EvoliRng, MutationStrategy};
+
+/// Quelltextdatei des Genoms innerhalb des Kandidatenprojekts
+const GENOME_FILE: &str = "src/lib.rs";
+
+/// Schweregrad einer Diagnose
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticLevel {
+    Error,
+    Warning,
+    Note,
+    Help,
+    Other(String),
+}
+
+impl DiagnosticLevel {
+    fn parse(level: &str) -> Self {
+        match level {
+            "error" | "error: internal compiler error" => DiagnosticLevel::Error,
+            "warning" => DiagnosticLevel::Warning,
+            "note" => DiagnosticLevel::Note,
+            "help" => DiagnosticLevel::Help,
+            other => DiagnosticLevel::Other(other.to_string()),
+        }
+    }
+}
+
+/// Position einer Diagnose im Quelltext
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiagnosticSpan {
+    pub file_name: String,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub line_start: usize,
+    pub column_start: usize,
+    pub is_primary: bool,
+}
+
+/// Vom Compiler vorgeschlagene Ersetzung; mehrteilige Vorschläge bestehen aus
+/// mehreren Ersetzungen mit derselben `group` innerhalb einer Diagnose
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub message: String,
+    pub span: DiagnosticSpan,
+    pub replacement: String,
+    pub machine_applicable: bool,
+    #[serde(default)]
+    pub group: usize,
+}
+
+/// Eine Compiler-Diagnose (Fehler oder Warnung) mit Vorschlägen
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    pub code: Option<String>,
+    pub message: String,
+    pub spans: Vec<DiagnosticSpan>,
+    pub suggestions: Vec<Suggestion>,
+    pub rendered: String,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.level == DiagnosticLevel::Error
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.level == DiagnosticLevel::Warning
+    }
+}
+
+// Rohformat der JSON-Ausgabe von cargo/rustc
+#[derive(Deserialize)]
+struct RawCargoMessage {
+    reason: String,
+    message: Option<RawDiagnostic>,
+    target: Option<RawTarget>,
+    #[serde(default)]
+    filenames: Vec<String>,
+    executable: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawTarget {
+    name: String,
+    kind: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RawDiagnostic {
+    message: String,
+    code: Option<RawCode>,
+    level: String,
+    #[serde(default)]
+    spans: Vec<RawSpan>,
+    #[serde(default)]
+    children: Vec<RawDiagnostic>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawCode {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct RawSpan {
+    file_name: String,
+    byte_start: usize,
+    byte_end: usize,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+    suggested_replacement: Option<String>,
+    suggestion_applicability: Option<String>,
+}
+
+impl RawSpan {
+    fn to_span(&self) -> DiagnosticSpan {
+        DiagnosticSpan {
+            file_name: self.file_name.clone(),
+            byte_start: self.byte_start,
+            byte_end: self.byte_end,
+            line_start: self.line_start,
+            column_start: self.column_start,
+            is_primary: self.is_primary,
+        }
+    }
+}
+
+impl RawDiagnostic {
+    /// Sammelt Vorschläge aus dieser Diagnose und allen Unterdiagnosen; jede
+    /// (Unter-)Diagnose bildet eine eigene Gruppe
+    fn collect_suggestions(&self, suggestions: &mut Vec<Suggestion>, groups: &mut usize) {
+        let group = *groups;
+        *groups += 1;
+        for span in &self.spans {
+            if let Some(replacement) = &span.suggested_replacement {
+                suggestions.push(Suggestion {
+                    message: self.message.clone(),
+                    span: span.to_span(),
+                    replacement: replacement.clone(),
+                    machine_applicable: span.suggestion_applicability.as_deref() == Some("MachineApplicable"),
+                    group,
+                });
+            }
+        }
+        for child in &self.children {
+            child.collect_suggestions(suggestions, groups);
+        }
+    }
+
+    fn into_diagnostic(self) -> Diagnostic {
+        let mut suggestions = Vec::new();
+        self.collect_suggestions(&mut suggestions, &mut 0);
+
+        Diagnostic {
+            level: DiagnosticLevel::parse(&self.level),
+            code: self.code.map(|code| code.code),
+            message: self.message,
+            spans: self.spans.iter().map(RawSpan::to_span).collect(),
+            suggestions,
+            rendered: self.rendered.unwrap_or_default(),
+        }
+    }
+}
+
+/// Ausgewertete JSON-Ausgabe eines cargo-Builds
+#[derive(Debug, Default)]
+pub struct CargoMessages {
+    pub diagnostics: Vec<Diagnostic>,
+    pub artifacts: Vec<String>, // Dateien der Bibliothek `lib_name`
+    pub executables: Vec<String>, // Ausführbare Dateien (z.B. Test-Binärdateien) der Bibliothek
+}
+
+/// Übersetzt die zeilenweise JSON-Ausgabe von `cargo build --message-format=json`
+pub fn parse_cargo_messages(stdout: &str, lib_name: &str) -> CargoMessages {
+    let mut messages = CargoMessages::default();
+
+    for line in stdout.lines().filter(|line| line.starts_with('{')) {
+        let Ok(raw) = serde_json::from_str::<RawCargoMessage>(line) else {
+            continue;
+        };
+
+        match raw.reason.as_str() {
+            "compiler-message" => {
+                // Zusammenfassungen wie "2 warnings emitted" haben keine Position
+                if let Some(message) = raw.message.filter(|message| !message.spans.is_empty()) {
+                    messages.diagnostics.push(message.into_diagnostic());
+                }
+            },
+            "compiler-artifact" => {
+                let is_lib = raw.target.as_ref()
+                    .map(|target| target.name.replace('-', "_") == lib_name && target.kind.iter().any(|kind| kind.contains("lib")))
+                    .unwrap_or(false);
+                if is_lib {
+                    messages.artifacts.extend(raw.filenames);
+                    messages.executables.extend(raw.executable);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    messages
+}
+
+/// Eine einzelne Ersetzung: Bytebereich und neuer Text
+type Edit<'a> = (usize, usize, &'a str);
+
+/// Überschneiden sich zwei Ersetzungen? Zwei Einfügungen an derselben Stelle ebenfalls,
+/// da ihre Reihenfolge offen wäre.
+fn edits_conflict(a: &Edit, b: &Edit) -> bool {
+    (a.0 == a.1 && b.0 == b.1 && a.0 == b.0) || (a.0 < b.1 && b.0 < a.1)
+}
+
+/// Wendet alle maschinell anwendbaren Vorschläge im Genom an. Mehrteilige Vorschläge
+/// werden ganz oder gar nicht übernommen; doppelt gemeldete Vorschläge (z.B. derselbe
+/// `use` für mehrere unaufgelöste Namen) nur einmal, überlappende gar nicht.
+/// Gibt `None` zurück, wenn es nichts anzuwenden gab.
+pub fn apply_suggestions(code: &str, diagnostics: &[Diagnostic]) -> Option<String> {
+    let applicable = |s: &Suggestion| {
+        s.machine_applicable
+            && s.span.file_name == GENOME_FILE
+            && s.span.byte_start <= s.span.byte_end
+            && s.span.byte_end <= code.len()
+            && code.is_char_boundary(s.span.byte_start)
+            && code.is_char_boundary(s.span.byte_end)
+    };
+
+    let mut edits: Vec<Edit> = Vec::new();
+    for diagnostic in diagnostics {
+        let mut groups: BTreeMap<usize, Vec<&Suggestion>> = BTreeMap::new();
+        for suggestion in &diagnostic.suggestions {
+            groups.entry(suggestion.group).or_default().push(suggestion);
+        }
+        for parts in groups.values() {
+            if !parts.iter().all(|part| applicable(part)) {
+                continue;
+            }
+            let mut group: Vec<Edit> = parts.iter()
+                .map(|part| (part.span.byte_start, part.span.byte_end, part.replacement.as_str()))
+                .collect();
+            group.sort();
+            group.dedup();
+            group.retain(|edit| !edits.contains(edit));
+
+            let conflicts = group.iter().enumerate().any(|(i, edit)| {
+                group[..i].iter().chain(edits.iter()).any(|other| edits_conflict(edit, other))
+            });
+            if !conflicts {
+                edits.extend(group);
+            }
+        }
+    }
+    if edits.is_empty() {
+        return None;
+    }
+
+    // Von hinten anwenden, damit die Positionen der übrigen Ersetzungen gültig bleiben
+    edits.sort_by_key(|&(start, end, _)| std::cmp::Reverse((start, end)));
+    let mut repaired = code.to_string();
+    for (start, end, replacement) in edits {
+        repaired.replace_range(start..end, replacement);
+    }
+
+    (repaired != code).then_some(repaired)
+}
+
+/// Diagnosen eines bewerteten Kandidaten
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CandidateReport {
+    pub generation: u64,
+    pub origin: String,
+    pub success: bool,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Aggregierte Fehlerstatistik über alle Kandidaten-Builds
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DiagnosticStats {
+    pub builds: u64,
+    pub failed_builds: u64,
+    pub repaired_builds: u64,
+    pub errors_by_code: BTreeMap<String, u64>,
+    pub warnings_by_code: BTreeMap<String, u64>,
+    pub recent: VecDeque<CandidateReport>, // Die letzten Berichte (begrenzt)
+}
+
+impl DiagnosticStats {
+    const MAX_RECENT: usize = 50;
+
+    /// Nimmt den Bericht eines Builds in die Statistik auf
+    pub fn record(&mut self, report: CandidateReport) {
+        self.builds += 1;
+        if !report.success {
+            self.failed_builds += 1;
+        }
+
+        for diagnostic in &report.diagnostics {
+            let code = diagnostic.code.clone().unwrap_or_else(|| "ohne Code".to_string());
+            if diagnostic.is_error() {
+                *self.errors_by_code.entry(code).or_insert(0) += 1;
+            } else if diagnostic.is_warning() {
+                *self.warnings_by_code.entry(code).or_insert(0) += 1;
+            }
+        }
+
+        self.recent.push_back(report);
+        while self.recent.len() > Self::MAX_RECENT {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Die häufigsten Fehlercodes, absteigend
+    pub fn top_errors(&self, n: usize) -> Vec<(String, u64)> {
+        let mut errors: Vec<(String, u64)> = self.errors_by_code.iter().map(|(c, n)| (c.clone(), *n)).collect();
+        errors.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        errors.truncate(n);
+        errors
+    }
+}
+
+/// Baut den Kandidaten und wendet die maschinell anwendbaren Compiler-Vorschläge an.
+/// Rettet so Kandidaten, die nur knapp an der Kompilierung scheitern.
+pub struct RepairMutationStrategy {
+    sandbox: Arc<BuildSandbox>,
+}
+
+impl RepairMutationStrategy {
+    pub fn new(sandbox: Arc<BuildSandbox>) -> Self {
+        RepairMutationStrategy { sandbox }
+    }
+
+    /// Reparatur anhand bereits vorliegender Diagnosen (ohne erneuten Build)
+    pub fn repair(code: &str, diagnostics: &[Diagnostic]) -> Option<String> {
+        apply_suggestions(code, diagnostics)
+    }
+}
+
+impl MutationStrategy for RepairMutationStrategy {
+    fn mutate(&self, code: &str, _rng: &mut EvoliRng) -> String {
+        match self.sandbox.check(code) {
+            Ok(diagnostics) => Self::repair(code, &diagnostics).unwrap_or_else(|| code.to_string()),
+            Err(_) => code.to_string(),
+        }
+    }
+
+    fn name(&self) -> String {
+        "RepairMutation".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNUSED_MUT: &str = r#"{"reason":"compiler-message","package_id":"x","message":{"message":"variable does not need to be mutable","code":{"code":"unused_mut","explanation":null},"level":"warning","spans":[{"file_name":"src/lib.rs","byte_start":13,"byte_end":18,"line_start":1,"line_end":1,"column_start":14,"column_end":19,"is_primary":true,"text":[],"label":null,"suggested_replacement":null,"suggestion_applicability":null,"expansion":null}],"children":[{"message":"remove this `mut`","code":null,"level":"help","spans":[{"file_name":"src/lib.rs","byte_start":13,"byte_end":17,"line_start":1,"line_end":1,"column_start":14,"column_end":18,"is_primary":true,"text":[],"label":null,"suggested_replacement":"","suggestion_applicability":"MachineApplicable","expansion":null}],"children":[],"rendered":null}],"rendered":"warning: variable does not need to be mutable\n"}}"#;
+
+    #[test]
+    fn test_parse_and_apply_suggestion() {
+        let stdout = format!(
+            "{}\n{}\n",
+            UNUSED_MUT,
+            r#"{"reason":"compiler-artifact","package_id":"x","target":{"name":"kern","kind":["lib"]},"filenames":["/t/libkern.rlib"]}"#
+        );
+        let messages = parse_cargo_messages(&stdout, "kern");
+        assert_eq!(messages.artifacts, vec!["/t/libkern.rlib".to_string()]);
+        assert_eq!(messages.diagnostics.len(), 1);
+
+        let diagnostic = &messages.diagnostics[0];
+        assert!(diagnostic.is_warning());
+        assert_eq!(diagnostic.code.as_deref(), Some("unused_mut"));
+        assert!(diagnostic.suggestions[0].machine_applicable);
+
+        let code = "fn f() { let mut x = 1; let _ = x; }";
+        let repaired = RepairMutationStrategy::repair(code, &messages.diagnostics).unwrap();
+        assert_eq!(repaired, "fn f() { let x = 1; let _ = x; }");
+    }
+
+    #[test]
+    fn test_duplicate_and_conflicting_suggestions() {
+        let suggestion = |byte_start, byte_end, replacement: &str, group| Suggestion {
+            message: "help".to_string(),
+            span: DiagnosticSpan {
+                file_name: GENOME_FILE.to_string(),
+                byte_start,
+                byte_end,
+                line_start: 1,
+                column_start: byte_start + 1,
+                is_primary: true,
+            },
+            replacement: replacement.to_string(),
+            machine_applicable: true,
+            group,
+        };
+        let diagnostic = |suggestions| Diagnostic {
+            level: DiagnosticLevel::Error,
+            code: Some("E0433".to_string()),
+            message: "failed to resolve".to_string(),
+            spans: Vec::new(),
+            suggestions,
+            rendered: String::new(),
+        };
+        let import = "use std::collections::HashMap;\n";
+        let code = "fn f() -> HashMap<u8, u8> { HashMap::new() }";
+
+        // Derselbe Import aus zwei Diagnosen wird nur einmal eingefügt; der zweiteilige
+        // Vorschlag kollidiert mit einem Teil an Offset 0 und entfällt komplett
+        let diagnostics = vec![
+            diagnostic(vec![suggestion(0, 0, import, 1)]),
+            diagnostic(vec![suggestion(0, 0, import, 1)]),
+            diagnostic(vec![suggestion(0, 0, "use x::HashMap;\n", 1), suggestion(10, 17, "BTreeMap", 1)]),
+        ];
+        let repaired = apply_suggestions(code, &diagnostics).unwrap();
+        assert_eq!(repaired, format!("{}{}", import, code));
+    }
+
+    #[test]
+    fn test_stats_aggregate_error_codes() {
+        let error = Diagnostic {
+            level: DiagnosticLevel::Error,
+            code: Some("E0308".to_string()),
+            message: "mismatched types".to_string(),
+            spans: Vec::new(),
+            suggestions: Vec::new(),
+            rendered: String::new(),
+        };
+        let mut stats = DiagnosticStats::default();
+        for _ in 0..2 {
+            stats.record(CandidateReport {
+                generation: 1,
+                origin: "Test".to_string(),
+                success: false,
+                diagnostics: vec![error.clone()],
+            });
+        }
+
+        assert_eq!(stats.failed_builds, 2);
+        assert_eq!(stats.top_errors(1), vec![("E0308".to_string(), 2)]);
+    }
+}
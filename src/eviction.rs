@@ -0,0 +1,421 @@
+// src/eviction.rs - Wertbasierte Speicherbereinigung
+//
+// Statt der ältesten Einträge in Cache und Wissensbasis (`ContentStore`) werden die am
+// wenigsten wertvollen gelöscht. Der Wert eines Eintrags ergibt sich aus seiner Kategorie,
+// der Zahl der Zugriffe, dem letzten Zugriff (Halbwertszeit `RECENCY_HALF_LIFE_HOURS`),
+// wie oft er in ein Genom eingeflossen ist, und seiner Größe. Zugriffe verzeichnet der
+// Kern im `AccessLedger`.
+//
+// Zuerst wird jede Kategorie mit Quote (`storage.quotas`) auf ihre Quote zurückgeführt,
+// danach alle löschbaren Kategorien gemeinsam auf `storage.cleanup_target`, sofern die
+// Gesamtbelegung `storage.cleanup_threshold` übersteigt. Einträge, deren Name oder Pfad
+// (relativ zu `paths.data_root`) auf ein Muster in `storage.pinned` passt, werden nie
+// gelöscht. Mit `storage.dry_run` wird der Plan nur protokolliert.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::StorageConfig;
+use crate::content_store::ContentStore;
+use crate::storage_accounting::{StorageCategory, StorageUsage};
+
+/// Kategorien, deren Einträge gelöscht werden dürfen
+pub const EVICTABLE_CATEGORIES: [StorageCategory; 2] = [StorageCategory::Cache, StorageCategory::Knowledge];
+
+/// Nach dieser Zeit ohne Zugriff zählt ein Eintrag nur noch halb so viel
+pub const RECENCY_HALF_LIFE_HOURS: f64 = 24.0;
+
+/// Höchstzahl der einzeln aufgeführten Einträge in `EvictionPlan::fmt`
+const LISTED_EVICTIONS: usize = 20;
+
+/// Nutzung eines Eintrags
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessRecord {
+    pub accesses: u64,     // Schreiben und Lesen durch den Kern
+    pub integrations: u64, // In ein Genom übernommen
+    pub last_access: i64,  // Unix-Sekunden; 0 = unbekannt, dann gilt die Abrufzeit
+}
+
+/// Zugriffe auf Einträge von Cache und Wissensbasis, persistent im Zustandsverzeichnis
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AccessLedger {
+    pub records: BTreeMap<String, AccessRecord>,
+}
+
+impl AccessLedger {
+    /// Lädt das Verzeichnis; fehlt es oder ist es unlesbar, beginnt es leer
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&AccessRecord> {
+        self.records.get(path.to_string_lossy().as_ref())
+    }
+
+    pub fn record_access(&mut self, path: &Path, now: i64) {
+        let record = self.records.entry(path.to_string_lossy().into_owned()).or_default();
+        record.accesses += 1;
+        record.last_access = now;
+    }
+
+    pub fn record_integration(&mut self, path: &Path, now: i64) {
+        let record = self.records.entry(path.to_string_lossy().into_owned()).or_default();
+        record.integrations += 1;
+        record.last_access = now;
+    }
+
+    /// Vergisst einen gelöschten Eintrag
+    pub fn forget(&mut self, path: &Path) {
+        self.records.remove(path.to_string_lossy().as_ref());
+    }
+}
+
+/// Ein löschbarer Eintrag mit seiner Nutzung
+#[derive(Clone, Debug)]
+pub struct EvictionItem {
+    pub path: PathBuf, // Logischer Pfad, siehe `ContentStore::entry_path`
+    pub category: StorageCategory,
+    pub bytes: u64,
+    pub record: AccessRecord,
+    pub pinned: bool,
+}
+
+impl EvictionItem {
+    /// Wert des Eintrags; niedrigere Werte werden zuerst gelöscht
+    pub fn value(&self, now: i64) -> f64 {
+        let age_hours = (now - self.record.last_access).max(0) as f64 / 3600.0;
+        let recency = 0.5f64.powf(age_hours / RECENCY_HALF_LIFE_HOURS);
+        let frequency = 1.0 + (self.record.accesses as f64).ln_1p();
+        let integration = 1.0 + 2.0 * self.record.integrations as f64;
+        let size = 1.0 + (self.bytes as f64 / (64.0 * 1024.0)).ln_1p();
+        category_weight(self.category) * frequency * integration * recency / size
+    }
+}
+
+/// Gewichtung je Kategorie: Seiten im Cache lassen sich erneut laden, extrahiertes Wissen nicht
+fn category_weight(category: StorageCategory) -> f64 {
+    match category {
+        StorageCategory::Knowledge => 4.0,
+        _ => 1.0,
+    }
+}
+
+/// Alle Einträge eines Inhaltsspeichers. Als Größe zählt der komprimierte Inhalt,
+/// sofern kein anderer Eintrag ihn teilt; sonst gibt das Löschen nichts frei.
+pub fn collect_entries(
+    store: &ContentStore,
+    category: StorageCategory,
+    ledger: &AccessLedger,
+    policy: &EvictionPolicy,
+) -> Vec<EvictionItem> {
+    store
+        .entries
+        .values()
+        .map(|entry| {
+            let path = store.entry_path(&entry.name);
+            let mut record = ledger.get(&path).cloned().unwrap_or_default();
+            if record.last_access == 0 {
+                record.last_access = entry.fetched_timestamp().unwrap_or(0);
+            }
+            let bytes = if store.references(&entry.hash) == 1 { entry.stored_size } else { 0 };
+            let pinned = policy.is_pinned(&path);
+            EvictionItem { path, category, bytes, record, pinned }
+        })
+        .collect()
+}
+
+/// Grund einer Löschung
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvictionReason {
+    Quota, // Kategorie über ihrer Quote
+    Limit, // Gesamtbelegung über `cleanup_threshold`
+}
+
+/// Eine geplante Löschung
+#[derive(Clone, Debug)]
+pub struct EvictionCandidate {
+    pub path: PathBuf,
+    pub category: StorageCategory,
+    pub bytes: u64,
+    pub value: f64,
+    pub reason: EvictionReason,
+}
+
+/// Ergebnis von `EvictionPolicy::plan`
+#[derive(Clone, Debug, Default)]
+pub struct EvictionPlan {
+    pub evictions: Vec<EvictionCandidate>,
+    pub usage_before: u64,
+    pub usage_after: u64,
+    pub pinned_bytes: u64,      // Von angehefteten Einträgen belegt
+    pub unevictable_bytes: u64, // Von Kategorien außerhalb von `EVICTABLE_CATEGORIES` belegt
+    pub remaining_over: u64,    // Trotz Plan noch über Quote bzw. Ziel
+}
+
+impl EvictionPlan {
+    pub fn is_empty(&self) -> bool {
+        self.evictions.is_empty()
+    }
+
+    pub fn freed_bytes(&self) -> u64 {
+        self.evictions.iter().map(|eviction| eviction.bytes).sum()
+    }
+}
+
+impl fmt::Display for EvictionPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} Einträge, {}KB ({}MB → {}MB)",
+            self.evictions.len(),
+            self.freed_bytes() / 1024,
+            self.usage_before / (1024 * 1024),
+            self.usage_after / (1024 * 1024)
+        )?;
+        for eviction in self.evictions.iter().take(LISTED_EVICTIONS) {
+            write!(
+                f,
+                "\n  {} ({}, {}KB, Wert {:.4}, {})",
+                eviction.path.display(),
+                eviction.category.as_str(),
+                eviction.bytes / 1024,
+                eviction.value,
+                match eviction.reason {
+                    EvictionReason::Quota => "Quote",
+                    EvictionReason::Limit => "Speichergrenze",
+                }
+            )?;
+        }
+        if self.evictions.len() > LISTED_EVICTIONS {
+            write!(f, "\n  ... und {} weitere", self.evictions.len() - LISTED_EVICTIONS)?;
+        }
+        if self.remaining_over > 0 {
+            write!(
+                f,
+                "\n  {}KB bleiben über Quote bzw. Ziel (nicht löschbar: {}KB angeheftet, {}MB in anderen Kategorien)",
+                self.remaining_over / 1024,
+                self.pinned_bytes / 1024,
+                self.unevictable_bytes / (1024 * 1024)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Schwellen, Quoten und angeheftete Muster aus `StorageConfig`
+#[derive(Clone, Debug, Default)]
+pub struct EvictionPolicy {
+    pub threshold: u64,
+    pub target: u64,
+    pub quotas: BTreeMap<StorageCategory, u64>,
+    pub pinned: Vec<String>,
+    pub base: PathBuf, // Muster gelten relativ zu diesem Verzeichnis
+}
+
+impl EvictionPolicy {
+    pub fn from_config(storage: &StorageConfig, base: &Path) -> Self {
+        let quotas = storage
+            .quotas
+            .iter()
+            .filter_map(|(name, bytes)| {
+                EVICTABLE_CATEGORIES.iter().find(|category| category.as_str() == name).map(|category| (*category, *bytes))
+            })
+            .collect();
+        EvictionPolicy {
+            threshold: storage.cleanup_threshold_bytes(),
+            target: storage.cleanup_target_bytes(),
+            quotas,
+            pinned: storage.pinned.clone(),
+            base: base.to_path_buf(),
+        }
+    }
+
+    /// Passt Name oder Pfad relativ zu `base` auf ein angeheftetes Muster?
+    pub fn is_pinned(&self, path: &Path) -> bool {
+        let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        let relative = path.strip_prefix(&self.base).unwrap_or(path).to_string_lossy();
+        self.pinned.iter().any(|pattern| wildcard_match(pattern, &name) || wildcard_match(pattern, &relative))
+    }
+
+    /// Wählt die zu löschenden Einträge, wertloseste zuerst
+    pub fn plan(&self, mut items: Vec<EvictionItem>, usage: &StorageUsage, now: i64) -> EvictionPlan {
+        items.sort_by(|a, b| {
+            a.value(now).total_cmp(&b.value(now)).then(a.record.last_access.cmp(&b.record.last_access))
+        });
+
+        let mut plan = EvictionPlan {
+            usage_before: usage.total_bytes(),
+            pinned_bytes: items.iter().filter(|item| item.pinned).map(|item| item.bytes).sum(),
+            unevictable_bytes: usage
+                .categories
+                .iter()
+                .filter(|(category, _)| !EVICTABLE_CATEGORIES.contains(category))
+                .map(|(_, usage)| usage.bytes)
+                .sum(),
+            ..EvictionPlan::default()
+        };
+        let mut total = usage.total_bytes();
+        let mut category_bytes: BTreeMap<StorageCategory, u64> =
+            usage.categories.iter().map(|(category, usage)| (*category, usage.bytes)).collect();
+        let mut evicted = vec![false; items.len()];
+
+        // 1. Kategorien auf ihre Quote zurückführen
+        for (category, quota) in &self.quotas {
+            for (index, item) in items.iter().enumerate() {
+                if category_bytes.get(category).copied().unwrap_or(0) <= *quota {
+                    break;
+                }
+                if item.category == *category && !item.pinned && item.bytes > 0 {
+                    evicted[index] = true;
+                    schedule(item, EvictionReason::Quota, now, &mut plan, &mut total, &mut category_bytes);
+                }
+            }
+            plan.remaining_over += category_bytes.get(category).copied().unwrap_or(0).saturating_sub(*quota);
+        }
+
+        // 2. Gesamtbelegung auf das Ziel senken
+        if total > self.threshold {
+            for (index, item) in items.iter().enumerate() {
+                if total <= self.target {
+                    break;
+                }
+                if !item.pinned && item.bytes > 0 && !evicted[index] {
+                    schedule(item, EvictionReason::Limit, now, &mut plan, &mut total, &mut category_bytes);
+                }
+            }
+            plan.remaining_over += total.saturating_sub(self.target);
+        }
+
+        plan.usage_after = total;
+        plan
+    }
+}
+
+/// Nimmt `item` in den Plan auf und zieht seine Größe von den Summen ab
+fn schedule(
+    item: &EvictionItem,
+    reason: EvictionReason,
+    now: i64,
+    plan: &mut EvictionPlan,
+    total: &mut u64,
+    category_bytes: &mut BTreeMap<StorageCategory, u64>,
+) {
+    *total = total.saturating_sub(item.bytes);
+    let bytes = category_bytes.entry(item.category).or_default();
+    *bytes = bytes.saturating_sub(item.bytes);
+    plan.evictions.push(EvictionCandidate {
+        path: item.path.clone(),
+        category: item.category,
+        bytes: item.bytes,
+        value: item.value(now),
+        reason,
+    });
+}
+
+/// Einfache Platzhaltersuche: `*` steht für beliebig viele, `?` für genau ein Zeichen
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage_accounting::CategoryUsage;
+
+    const NOW: i64 = 1_800_000_000;
+    const HOUR: i64 = 3600;
+
+    fn item(path: &str, category: StorageCategory, bytes: u64, accesses: u64, integrations: u64, age_hours: i64) -> EvictionItem {
+        EvictionItem {
+            path: PathBuf::from(path),
+            category,
+            bytes,
+            record: AccessRecord { accesses, integrations, last_access: NOW - age_hours * HOUR },
+            pinned: false,
+        }
+    }
+
+    fn usage(items: &[EvictionItem]) -> StorageUsage {
+        let mut usage = StorageUsage::default();
+        for item in items {
+            let entry = usage.categories.entry(item.category).or_insert(CategoryUsage::default());
+            entry.bytes += item.bytes;
+            entry.files += 1;
+        }
+        usage
+    }
+
+    #[test]
+    fn test_useful_knowledge_outlives_junk_and_pins_are_kept() {
+        let mut items = vec![
+            item("evoli_knowledge/nützlich.rs", StorageCategory::Knowledge, 100, 12, 5, 72),
+            item("evoli_cache/gestern.html", StorageCategory::Cache, 100, 1, 0, 24),
+            item("evoli_cache/heute.html", StorageCategory::Cache, 100, 3, 0, 1),
+            item("evoli_knowledge/wichtig.rs", StorageCategory::Knowledge, 100, 1, 0, 500),
+        ];
+        let mut policy = EvictionPolicy {
+            threshold: 300,
+            target: 200,
+            pinned: vec!["wichtig*".to_string()],
+            ..EvictionPolicy::default()
+        };
+        items[3].pinned = policy.is_pinned(&items[3].path);
+        let usage = usage(&items);
+
+        let plan = policy.plan(items.clone(), &usage, NOW);
+        let evicted: Vec<_> = plan.evictions.iter().map(|e| e.path.to_string_lossy().into_owned()).collect();
+        assert_eq!(evicted, ["evoli_cache/gestern.html", "evoli_cache/heute.html"]);
+        assert_eq!((plan.usage_after, plan.pinned_bytes, plan.remaining_over), (200, 100, 0));
+
+        // Quote des Caches greift auch unterhalb der Gesamtschwelle
+        policy.threshold = 1000;
+        policy.quotas.insert(StorageCategory::Cache, 150);
+        let plan = policy.plan(items, &usage, NOW);
+        assert_eq!(plan.evictions.len(), 1);
+        assert_eq!(plan.evictions[0].reason, EvictionReason::Quota);
+        assert!(plan.to_string().contains("gestern.html"));
+    }
+
+    #[test]
+    fn test_wildcards_and_ledger() {
+        assert!(wildcard_match("*.rs", "evoli_knowledge_1.rs"));
+        assert!(wildcard_match("evoli_knowledge/*", "evoli_knowledge/a/b.rs"));
+        assert!(wildcard_match("gen_?.rs", "gen_7.rs"));
+        assert!(!wildcard_match("*.rs", "seite.html"));
+
+        let mut ledger = AccessLedger::default();
+        let path = Path::new("evoli_knowledge/a.rs");
+        ledger.record_access(path, NOW);
+        ledger.record_integration(path, NOW + 1);
+        assert_eq!(ledger.get(path), Some(&AccessRecord { accesses: 1, integrations: 1, last_access: NOW + 1 }));
+        ledger.forget(path);
+        assert!(ledger.get(path).is_none());
+    }
+}
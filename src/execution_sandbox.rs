@@ -0,0 +1,272 @@
+// src/execution_sandbox.rs - Ausführung erzeugter Binärdateien mit Ressourcenlimits
+//
+// Jede Ausführung läuft in einem eigenen, leeren Arbeitsverzeichnis, mit
+// rlimits für CPU-Zeit, Speicher und Dateigröße, einer Wanduhr-Zeitgrenze
+// (beendet die ganze Prozessgruppe) und (unter Linux) in eigenem Netzwerk-Namespace
+// ohne Netzwerkschnittstellen. Die Messwerte stammen aus `wait4`.
+//
+// `RLIMIT_NPROC` wird bewusst nicht gesetzt: es zählt alle Prozesse und Threads der
+// Benutzerkennung, nicht nur die der Sandbox. Ob Namespaces verfügbar sind, prüft
+// `probe_network_isolation` einmal beim Start.
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(target_os = "linux")]
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Zähler für eindeutige Arbeitsverzeichnisse innerhalb eines Prozesses
+static SCRATCH_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Höchstmenge an mitgeschnittener Ausgabe je Kanal
+const MAX_CAPTURED_BYTES: u64 = 1024 * 1024;
+
+/// Ressourcengrenzen einer Ausführung
+#[derive(Clone, Debug)]
+pub struct ExecutionLimits {
+    pub cpu_time: Duration,   // RLIMIT_CPU
+    pub memory_bytes: u64,    // RLIMIT_AS
+    pub file_size_bytes: u64, // RLIMIT_FSIZE
+    pub wall_clock: Duration, // Danach wird die Prozessgruppe beendet
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        ExecutionLimits {
+            cpu_time: Duration::from_secs(30),
+            memory_bytes: 2 * 1024 * 1024 * 1024,
+            file_size_bytes: 64 * 1024 * 1024,
+            wall_clock: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Ergebnis einer Ausführung in der Sandbox
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ExecutionResult {
+    pub exit_code: Option<i32>, // `None`, wenn der Prozess durch ein Signal endete
+    pub signal: Option<i32>,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+    pub wall_time: Duration,
+    pub cpu_time: Duration,     // Benutzer- plus Systemzeit
+    pub max_rss_bytes: u64,     // Höchster Speicherverbrauch (Resident Set Size)
+}
+
+impl ExecutionResult {
+    /// Regulär mit Exit-Code 0 beendet
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0) && !self.timed_out
+    }
+}
+
+/// Führt Binärdateien isoliert und mit Ressourcenlimits aus
+#[derive(Clone, Debug)]
+pub struct ExecutionSandbox {
+    pub limits: ExecutionLimits,
+    pub scratch_root: PathBuf, // Hier werden die Arbeitsverzeichnisse angelegt
+    pub deny_network: bool,
+}
+
+impl Default for ExecutionSandbox {
+    fn default() -> Self {
+        ExecutionSandbox {
+            limits: ExecutionLimits::default(),
+            scratch_root: std::env::temp_dir(),
+            deny_network: true,
+        }
+    }
+}
+
+impl ExecutionSandbox {
+    /// Führt `binary` mit `args` aus; das Arbeitsverzeichnis wird danach entfernt
+    pub fn run(&self, binary: &Path, args: &[&str]) -> Result<ExecutionResult, Box<dyn std::error::Error>> {
+        let binary = binary.canonicalize()?;
+        let scratch = self.scratch_root.join(format!(
+            "evoli_run_{}_{}",
+            std::process::id(),
+            SCRATCH_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&scratch)?;
+
+        let mut command = Command::new(&binary);
+        command
+            .args(args)
+            .current_dir(&scratch)
+            .env_clear()
+            .env("PATH", "/usr/bin:/bin")
+            .env("HOME", &scratch)
+            .env("TMPDIR", &scratch)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let result = self.spawn_and_wait(command);
+        fs::remove_dir_all(&scratch).ok();
+        result
+    }
+
+    #[cfg(target_os = "linux")]
+    fn spawn_and_wait(&self, mut command: Command) -> Result<ExecutionResult, Box<dyn std::error::Error>> {
+        use std::os::unix::process::CommandExt;
+
+        let limits = self.limits.clone();
+        let deny_network = self.deny_network;
+        // Eigene Prozessgruppe, damit bei Zeitüberschreitung auch Kindprozesse enden
+        command.process_group(0);
+        // SAFETY: Zwischen fork und exec werden nur async-signal-sichere Systemaufrufe verwendet
+        unsafe {
+            command.pre_exec(move || {
+                apply_limits(&limits)?;
+                if deny_network && libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+
+        let start = Instant::now();
+        let mut child = command.spawn()?;
+        let pid = child.id() as libc::pid_t;
+        let stdout = capture(child.stdout.take());
+        let stderr = capture(child.stderr.take());
+
+        let mut result = ExecutionResult::default();
+        let mut status: libc::c_int = 0;
+        // SAFETY: `rusage` ist ein reines C-Struct, für das Nullbytes gültig sind
+        let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+        loop {
+            // SAFETY: `pid` ist unser eigenes, noch nicht abgeholtes Kind
+            let waited = unsafe { libc::wait4(pid, &mut status, libc::WNOHANG, &mut usage) };
+            if waited == pid {
+                break;
+            }
+            if waited < 0 {
+                return Err(std::io::Error::last_os_error().into());
+            }
+            if start.elapsed() >= self.limits.wall_clock {
+                result.timed_out = true;
+                // SAFETY: Signal an die eigene Prozessgruppe des Kindes
+                unsafe { libc::kill(-pid, libc::SIGKILL) };
+                // SAFETY: siehe oben; wartet blockierend auf das beendete Kind
+                unsafe { libc::wait4(pid, &mut status, 0, &mut usage) };
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        result.wall_time = start.elapsed();
+
+        if libc::WIFEXITED(status) {
+            result.exit_code = Some(libc::WEXITSTATUS(status));
+        } else if libc::WIFSIGNALED(status) {
+            result.signal = Some(libc::WTERMSIG(status));
+        }
+        result.cpu_time = timeval_to_duration(usage.ru_utime) + timeval_to_duration(usage.ru_stime);
+        result.max_rss_bytes = (usage.ru_maxrss.max(0) as u64) * 1024; // Linux liefert KiB
+        result.stdout = stdout.join().unwrap_or_default();
+        result.stderr = stderr.join().unwrap_or_default();
+
+        Ok(result)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn spawn_and_wait(&self, _command: Command) -> Result<ExecutionResult, Box<dyn std::error::Error>> {
+        Err("Die Ausführungs-Sandbox ist nur unter Linux verfügbar".into())
+    }
+}
+
+/// Prüft, ob Kindprozesse einen eigenen Netzwerk-Namespace erhalten können. Das scheitert
+/// mit EPERM, wo unprivilegierte User-Namespaces gesperrt sind (z.B. Dockers Standard-Seccomp-Profil,
+/// AppArmor unter Ubuntu 24.04); `Err` enthält dann die Begründung.
+/// Das Ergebnis wird je Prozess nur einmal ermittelt.
+#[cfg(target_os = "linux")]
+pub fn probe_network_isolation() -> Result<(), String> {
+    static PROBE: OnceLock<Result<(), String>> = OnceLock::new();
+    PROBE.get_or_init(|| {
+        let sandbox = ExecutionSandbox {
+            limits: ExecutionLimits { wall_clock: Duration::from_secs(5), ..ExecutionLimits::default() },
+            ..ExecutionSandbox::default()
+        };
+        match sandbox.run(Path::new("/bin/true"), &[]) {
+            Ok(result) if result.success() => Ok(()),
+            Ok(result) => Err(format!("Probelauf endete mit {:?}", result.exit_code.or(result.signal))),
+            Err(e) => Err(format!("kein eigener Netzwerk-Namespace möglich ({})", e)),
+        }
+    }).clone()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn probe_network_isolation() -> Result<(), String> {
+    Err("Die Ausführungs-Sandbox ist nur unter Linux verfügbar".to_string())
+}
+
+/// Setzt die rlimits im Kindprozess (zwischen fork und exec)
+#[cfg(target_os = "linux")]
+fn apply_limits(limits: &ExecutionLimits) -> std::io::Result<()> {
+    let cpu_seconds = limits.cpu_time.as_secs().max(1);
+    let settings = [
+        (libc::RLIMIT_CPU, cpu_seconds),
+        (libc::RLIMIT_AS, limits.memory_bytes),
+        (libc::RLIMIT_FSIZE, limits.file_size_bytes),
+    ];
+    for (resource, value) in settings {
+        let limit = libc::rlimit { rlim_cur: value as libc::rlim_t, rlim_max: value as libc::rlim_t };
+        // SAFETY: setrlimit ist async-signal-sicher und erhält einen gültigen Zeiger
+        if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn timeval_to_duration(time: libc::timeval) -> Duration {
+    Duration::from_secs(time.tv_sec.max(0) as u64) + Duration::from_micros(time.tv_usec.max(0) as u64)
+}
+
+/// Liest einen Ausgabekanal in einem eigenen Thread (begrenzt, der Rest wird verworfen)
+fn capture<R: Read + Send + 'static>(pipe: Option<R>) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let Some(mut pipe) = pipe else {
+            return String::new();
+        };
+        let mut buffer = Vec::new();
+        (&mut pipe).take(MAX_CAPTURED_BYTES).read_to_end(&mut buffer).ok();
+        std::io::copy(&mut pipe, &mut std::io::sink()).ok();
+        String::from_utf8_lossy(&buffer).into_owned()
+    })
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_captures_output_and_enforces_timeout() {
+        let sandbox = ExecutionSandbox {
+            limits: ExecutionLimits { wall_clock: Duration::from_millis(500), ..ExecutionLimits::default() },
+            ..ExecutionSandbox::default()
+        };
+
+        let result = sandbox.run(Path::new("/bin/sh"), &["-c", "pwd; echo fehler >&2; exit 3"]).unwrap();
+        assert_eq!(result.exit_code, Some(3));
+        assert!(result.stdout.contains("evoli_run_"));
+        assert_eq!(result.stderr.trim(), "fehler");
+        assert!(!result.timed_out);
+
+        // Im eigenen Netzwerk-Namespace gibt es nur die Loopback-Schnittstelle
+        assert_eq!(probe_network_isolation(), Ok(()));
+        let network = sandbox.run(Path::new("/bin/cat"), &["/proc/net/dev"]).unwrap();
+        assert_eq!(network.stdout.lines().filter(|line| line.contains(':')).count(), 1);
+
+        let slow = sandbox.run(Path::new("/bin/sh"), &["-c", "sleep 5"]).unwrap();
+        assert!(slow.timed_out);
+        assert_eq!(slow.signal, Some(libc::SIGKILL));
+        assert!(slow.wall_time < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,216 @@
+// src/fitness.rs - Eingebaute Fitness-Evaluatoren und gewichtete Aggregation
+//
+// Alle Evaluatoren liefern Werte im Bereich 0.0 (schlecht) bis 1.0 (gut) und
+// lesen ausschließlich die Messwerte aus `RuntimeMetrics`.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::{FitnessEvaluator, RuntimeMetrics};
+
+/// 1.0 bei fehlerfreier Kompilierung ohne Warnungen, 0.5 mit Warnungen, sonst 0.0
+pub struct CompilesCleanlyEvaluator;
+
+/// Weniger Compiler-Warnungen sind besser
+pub struct WarningCountEvaluator;
+
+/// Kleinere Binärdateien sind besser
+pub struct BinarySizeEvaluator {
+    pub reference_bytes: u64, // Größe, bei der der Wert 0.5 beträgt
+}
+
+/// Kürzere Kompilierzeiten sind besser
+pub struct CompileTimeEvaluator {
+    pub reference: Duration,
+}
+
+/// Anteil erfolgreicher Tests
+pub struct TestPassRatioEvaluator;
+
+/// Kürzere Laufzeit der Benchmark-Ausführung ist besser
+pub struct BenchmarkRuntimeEvaluator {
+    pub reference: Duration,
+}
+
+/// Kürzere Genome sind besser
+pub struct GenomeLengthEvaluator {
+    pub reference_bytes: usize,
+}
+
+/// Bildet `value` auf (0, 1] ab; beim Referenzwert ergibt sich 0.5
+fn inverse_score(value: f64, reference: f64) -> f64 {
+    if reference <= 0.0 {
+        return 0.0;
+    }
+    reference / (reference + value.max(0.0))
+}
+
+impl FitnessEvaluator for CompilesCleanlyEvaluator {
+    fn evaluate(&self, _code: &str, runtime_metrics: &RuntimeMetrics) -> f64 {
+        match (runtime_metrics.compile_success, runtime_metrics.warning_count) {
+            (true, 0) => 1.0,
+            (true, _) => 0.5,
+            (false, _) => 0.0,
+        }
+    }
+
+    fn name(&self) -> String {
+        "CompilesCleanly".to_string()
+    }
+}
+
+impl FitnessEvaluator for WarningCountEvaluator {
+    fn evaluate(&self, _code: &str, runtime_metrics: &RuntimeMetrics) -> f64 {
+        if !runtime_metrics.compile_success {
+            return 0.0;
+        }
+        inverse_score(runtime_metrics.warning_count as f64, 1.0)
+    }
+
+    fn name(&self) -> String {
+        "WarningCount".to_string()
+    }
+}
+
+impl FitnessEvaluator for BinarySizeEvaluator {
+    fn evaluate(&self, _code: &str, runtime_metrics: &RuntimeMetrics) -> f64 {
+        if !runtime_metrics.compile_success || runtime_metrics.binary_size == 0 {
+            return 0.0;
+        }
+        inverse_score(runtime_metrics.binary_size as f64, self.reference_bytes as f64)
+    }
+
+    fn name(&self) -> String {
+        "BinarySize".to_string()
+    }
+}
+
+impl FitnessEvaluator for CompileTimeEvaluator {
+    fn evaluate(&self, _code: &str, runtime_metrics: &RuntimeMetrics) -> f64 {
+        if !runtime_metrics.compile_success {
+            return 0.0;
+        }
+        inverse_score(runtime_metrics.compile_time.as_secs_f64(), self.reference.as_secs_f64())
+    }
+
+    fn name(&self) -> String {
+        "CompileTime".to_string()
+    }
+}
+
+impl FitnessEvaluator for TestPassRatioEvaluator {
+    fn evaluate(&self, _code: &str, runtime_metrics: &RuntimeMetrics) -> f64 {
+        let total = runtime_metrics.tests_passed + runtime_metrics.tests_failed;
+        if total == 0 {
+            return 0.0;
+        }
+        runtime_metrics.tests_passed as f64 / total as f64
+    }
+
+    fn name(&self) -> String {
+        "TestPassRatio".to_string()
+    }
+}
+
+impl FitnessEvaluator for BenchmarkRuntimeEvaluator {
+    fn evaluate(&self, _code: &str, runtime_metrics: &RuntimeMetrics) -> f64 {
+        match runtime_metrics.benchmark_runtime {
+            Some(runtime) => inverse_score(runtime.as_secs_f64(), self.reference.as_secs_f64()),
+            None => 0.0,
+        }
+    }
+
+    fn name(&self) -> String {
+        "BenchmarkRuntime".to_string()
+    }
+}
+
+impl FitnessEvaluator for GenomeLengthEvaluator {
+    fn evaluate(&self, code: &str, _runtime_metrics: &RuntimeMetrics) -> f64 {
+        inverse_score(code.len() as f64, self.reference_bytes as f64)
+    }
+
+    fn name(&self) -> String {
+        "GenomeLength".to_string()
+    }
+}
+
+/// Alle eingebauten Evaluatoren mit Standard-Referenzwerten
+pub fn default_evaluators() -> Vec<Box<dyn FitnessEvaluator>> {
+    vec![
+        Box::new(CompilesCleanlyEvaluator),
+        Box::new(WarningCountEvaluator),
+        Box::new(BinarySizeEvaluator { reference_bytes: 4 * 1024 * 1024 }),
+        Box::new(CompileTimeEvaluator { reference: Duration::from_secs(30) }),
+        Box::new(TestPassRatioEvaluator),
+        Box::new(BenchmarkRuntimeEvaluator { reference: Duration::from_secs(5) }),
+    ]
+}
+
+/// Standardziele der Mehrzieloptimierung: Geschwindigkeit, Binärgröße, Genomlänge, Warnungen
+pub fn default_objectives() -> Vec<Box<dyn FitnessEvaluator>> {
+    vec![
+        Box::new(BenchmarkRuntimeEvaluator { reference: Duration::from_secs(5) }),
+        Box::new(BinarySizeEvaluator { reference_bytes: 4 * 1024 * 1024 }),
+        Box::new(GenomeLengthEvaluator { reference_bytes: 64 * 1024 }),
+        Box::new(WarningCountEvaluator),
+    ]
+}
+
+/// Gewichteter Mittelwert aller Evaluatoren; fehlende Gewichte zählen als 1.0
+pub fn aggregate_fitness(
+    evaluators: &[Box<dyn FitnessEvaluator>],
+    weights: &HashMap<String, f64>,
+    code: &str,
+    runtime_metrics: &RuntimeMetrics,
+) -> f64 {
+    let mut weighted_sum = 0.0;
+    let mut total_weight = 0.0;
+
+    for evaluator in evaluators {
+        let weight = weights.get(&evaluator.name()).copied().unwrap_or(1.0).max(0.0);
+        weighted_sum += weight * evaluator.evaluate(code, runtime_metrics);
+        total_weight += weight;
+    }
+
+    if total_weight > 0.0 {
+        weighted_sum / total_weight
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_aggregation() {
+        let metrics = RuntimeMetrics {
+            compile_success: true,
+            warning_count: 1,
+            tests_passed: 3,
+            tests_failed: 1,
+            ..RuntimeMetrics::default()
+        };
+        let evaluators: Vec<Box<dyn FitnessEvaluator>> = vec![
+            Box::new(CompilesCleanlyEvaluator),
+            Box::new(TestPassRatioEvaluator),
+        ];
+
+        let equal = aggregate_fitness(&evaluators, &HashMap::new(), "", &metrics);
+        assert!((equal - 0.625).abs() < 1e-9);
+
+        let mut weights = HashMap::new();
+        weights.insert("CompilesCleanly".to_string(), 0.0);
+        let tests_only = aggregate_fitness(&evaluators, &weights, "", &metrics);
+        assert!((tests_only - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_failed_compilation_scores_zero() {
+        let metrics = RuntimeMetrics::default();
+        for evaluator in default_evaluators() {
+            assert_eq!(evaluator.evaluate("", &metrics), 0.0, "{}", evaluator.name());
+        }
+    }
+}
@@ -0,0 +1,328 @@
+// src/genome_store.rs - Inhaltsadressierter Genomspeicher mit Abstammungsgraph
+//
+// Jedes Genom wird unter dem SHA-256 seines Inhalts gzip-komprimiert abgelegt
+// (`objects/<2 Zeichen>/<Hash>.gz`), identische Genome also nur einmal. Der
+// Index (`index.json`) hält je Genom Eltern, erzeugenden Operator, Fitness und
+// Kompilierergebnis und bildet so einen gerichteten azyklischen Graphen.
+// Welches Genom in welcher Generation aktiv war, hält `history.json` fest.
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Hex-kodierter SHA-256 eines Genoms
+pub type GenomeId = String;
+
+/// Berechnet die Kennung eines Genoms
+pub fn genome_id(genome: &str) -> GenomeId {
+    Sha256::digest(genome.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Ein Knoten des Abstammungsgraphen
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GenomeRecord {
+    pub id: GenomeId,
+    pub parents: Vec<GenomeId>,
+    pub operator: String,        // Mutations-/Crossover-Operator (z.B. "ItemCrossover+LiteralMutation")
+    pub generation: u64,         // Generation, in der das Genom zuerst auftrat
+    pub fitness: Option<f64>,    // `None`, solange nicht bewertet
+    pub compiled: Option<bool>,  // `None`, solange nicht gebaut
+    pub size: usize,             // Unkomprimierte Größe in Bytes
+    pub created_at: String,
+}
+
+/// Art eines Wechsels des aktiven Genoms
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum HistoryEventKind {
+    Seed,     // Ausgangsgenom
+    Adopted,  // Übernahme durch die Evolution
+    Rollback, // Manuelle Rückkehr zu einer früheren Generation
+}
+
+/// Ein Wechsel des aktiven Genoms
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HistoryEvent {
+    pub generation: u64,
+    pub kind: HistoryEventKind,
+    pub genome: GenomeId,
+    pub previous: Option<GenomeId>,
+    pub fitness: f64,
+    pub timestamp: String,
+}
+
+/// Inhaltsadressierter Speicher aller Genome
+#[derive(Debug)]
+pub struct GenomeStore {
+    pub root: PathBuf,
+    pub records: BTreeMap<GenomeId, GenomeRecord>,
+    pub history: Vec<HistoryEvent>,     // Aktive Genome in zeitlicher Reihenfolge
+    pending: HashMap<GenomeId, String>, // Noch nicht geschriebene Genome
+}
+
+impl GenomeStore {
+    /// Öffnet den Speicher unter `root`; ein fehlender oder defekter Index ergibt einen leeren Speicher
+    pub fn open(root: &Path) -> Self {
+        let records = fs::read_to_string(root.join("index.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let history = fs::read_to_string(root.join("history.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        GenomeStore {
+            root: root.to_path_buf(),
+            records,
+            history,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Pfad des Index
+    pub fn index_path(&self) -> PathBuf {
+        self.root.join("index.json")
+    }
+
+    fn object_path(&self, id: &str) -> PathBuf {
+        self.root.join("objects").join(&id[..2]).join(format!("{}.gz", id))
+    }
+
+    /// Nimmt ein Genom auf; ist es bereits bekannt, werden nur neue Eltern ergänzt
+    pub fn put(&mut self, genome: &str, parents: &[GenomeId], operator: &str, generation: u64) -> GenomeId {
+        let id = genome_id(genome);
+
+        if let Some(record) = self.records.get_mut(&id) {
+            for parent in parents {
+                if *parent != id && !record.parents.contains(parent) {
+                    record.parents.push(parent.clone());
+                }
+            }
+            return id;
+        }
+
+        self.records.insert(id.clone(), GenomeRecord {
+            id: id.clone(),
+            parents: parents.iter().filter(|parent| **parent != id).cloned().collect(),
+            operator: operator.to_string(),
+            generation,
+            fitness: None,
+            compiled: None,
+            size: genome.len(),
+            created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        });
+        self.pending.insert(id.clone(), genome.to_string());
+        id
+    }
+
+    /// Hält Bewertung und Kompilierergebnis eines Genoms fest
+    pub fn set_outcome(&mut self, id: &str, fitness: f64, compiled: bool) {
+        if let Some(record) = self.records.get_mut(id) {
+            record.fitness = Some(fitness);
+            record.compiled = Some(compiled);
+        }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.records.contains_key(id)
+    }
+
+    /// Bekannt und bereits auf die Festplatte geschrieben
+    pub fn is_persisted(&self, id: &str) -> bool {
+        self.contains(id) && !self.pending.contains_key(id)
+    }
+
+    pub fn record(&self, id: &str) -> Option<&GenomeRecord> {
+        self.records.get(id)
+    }
+
+    /// Liest den Inhalt eines Genoms
+    pub fn get(&self, id: &str) -> Option<String> {
+        if let Some(genome) = self.pending.get(id) {
+            return Some(genome.clone());
+        }
+        if !self.contains(id) {
+            return None;
+        }
+
+        let file = fs::File::open(self.object_path(id)).ok()?;
+        let mut genome = String::new();
+        GzDecoder::new(file).read_to_string(&mut genome).ok()?;
+        Some(genome)
+    }
+
+    /// Hält fest, dass `genome` ab `generation` das aktive Genom ist
+    pub fn record_event(&mut self, generation: u64, kind: HistoryEventKind, genome: &str, fitness: f64) {
+        let previous = self.history.last().map(|event| event.genome.clone());
+        self.history.push(HistoryEvent {
+            generation,
+            kind,
+            genome: genome.to_string(),
+            previous,
+            fitness,
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        });
+    }
+
+    /// Das in `generation` aktive Genom (letzter Wechsel bis einschließlich `generation`)
+    pub fn active_at(&self, generation: u64) -> Option<&HistoryEvent> {
+        self.history.iter().rev().find(|event| event.generation <= generation)
+    }
+
+    /// Direkte Nachkommen eines Genoms
+    pub fn children(&self, id: &str) -> Vec<&GenomeRecord> {
+        self.records
+            .values()
+            .filter(|record| record.parents.iter().any(|parent| parent == id))
+            .collect()
+    }
+
+    /// Alle Vorfahren (Breitensuche, nächste zuerst)
+    pub fn ancestors(&self, id: &str) -> Vec<&GenomeRecord> {
+        self.walk(id, |record| record.parents.iter().map(String::as_str).collect())
+    }
+
+    /// Alle Nachkommen (Breitensuche, nächste zuerst)
+    pub fn descendants(&self, id: &str) -> Vec<&GenomeRecord> {
+        self.walk(id, |record| self.children(&record.id).into_iter().map(|child| child.id.as_str()).collect())
+    }
+
+    fn walk<'a, F>(&'a self, id: &str, next: F) -> Vec<&'a GenomeRecord>
+    where
+        F: Fn(&'a GenomeRecord) -> Vec<&'a str>,
+    {
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<&str> = self.records.get(id).map(&next).unwrap_or_default().into();
+        let mut found = Vec::new();
+
+        while let Some(current) = queue.pop_front() {
+            if !visited.insert(current) {
+                continue;
+            }
+            if let Some(record) = self.records.get(current) {
+                found.push(record);
+                queue.extend(next(record));
+            }
+        }
+        found
+    }
+
+    /// Obergrenze der Bytes, die `save` schreibt: neue Genome unkomprimiert plus Index und Verlauf
+    pub fn save_size(&self) -> Result<u64, Box<dyn std::error::Error>> {
+        let objects: usize = self.pending.values().map(String::len).sum();
+        let index = serde_json::to_vec(&self.records)?.len();
+        let history = serde_json::to_vec_pretty(&self.history)?.len();
+        Ok((objects + index + history) as u64)
+    }
+
+    /// Schreibt neue Genome komprimiert und danach den Index
+    pub fn save(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        for (id, genome) in &self.pending {
+            let path = self.object_path(id);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut encoder = GzEncoder::new(fs::File::create(&path)?, Compression::default());
+            encoder.write_all(genome.as_bytes())?;
+            encoder.finish()?;
+        }
+        self.pending.clear();
+
+        fs::create_dir_all(&self.root)?;
+        let temporary = self.index_path().with_extension("json.tmp");
+        fs::write(&temporary, serde_json::to_string(&self.records)?)?;
+        fs::rename(&temporary, self.index_path())?;
+        fs::write(self.root.join("history.json"), serde_json::to_string_pretty(&self.history)?)?;
+        Ok(())
+    }
+}
+
+/// Zeilenweiser Unterschied zweier Genome (`-` entfernt, `+` hinzugefügt), ohne unveränderte Zeilen
+pub fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Längste gemeinsame Teilfolge, von hinten aufgebaut
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push(format!("-{:>5} {}", i + 1, old[i]));
+            i += 1;
+        } else {
+            diff.push(format!("+{:>5} {}", j + 1, new[j]));
+            j += 1;
+        }
+    }
+    diff.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_deduplicates_and_tracks_lineage() {
+        let root = std::env::temp_dir().join(format!("evoli_genome_store_test_{}", std::process::id()));
+        let mut store = GenomeStore::open(&root);
+
+        let seed = store.put("fn a() {}", &[], "Seed", 0);
+        let other = store.put("fn b() {}", &[], "Seed", 0);
+        let child = store.put("fn a() { 1 }", std::slice::from_ref(&seed), "LiteralMutation", 1);
+        let cross = store.put("fn ab() {}", &[child.clone(), other.clone()], "ItemCrossover", 2);
+        assert_eq!(store.put("fn a() {}", &[], "Backup", 5), seed);
+        assert_eq!(store.records.len(), 4);
+        store.set_outcome(&cross, 0.5, true);
+        let pending_size = store.save_size().unwrap();
+        store.save().unwrap();
+        let written = |name: &str| fs::metadata(root.join(name)).unwrap().len();
+        assert_eq!(store.save_size().unwrap(), written("index.json") + written("history.json"));
+        assert!(pending_size > store.save_size().unwrap());
+
+        let reopened = GenomeStore::open(&root);
+        assert_eq!(reopened.get(&child).as_deref(), Some("fn a() { 1 }"));
+        assert_eq!(reopened.record(&seed).unwrap().operator, "Seed");
+        assert_eq!(reopened.record(&cross).unwrap().compiled, Some(true));
+
+        let ancestors: Vec<&str> = reopened.ancestors(&cross).iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ancestors, vec![child.as_str(), other.as_str(), seed.as_str()]);
+        let descendants: Vec<&str> = reopened.descendants(&seed).iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(descendants, vec![child.as_str(), cross.as_str()]);
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_history_and_line_diff() {
+        let mut store = GenomeStore::open(&std::env::temp_dir().join("evoli_genome_store_history_unbenutzt"));
+        store.record_event(0, HistoryEventKind::Seed, "a", 0.1);
+        store.record_event(3, HistoryEventKind::Adopted, "b", 0.4);
+        store.record_event(7, HistoryEventKind::Rollback, "a", 0.1);
+        assert_eq!(store.active_at(2).unwrap().genome, "a");
+        assert_eq!(store.active_at(5).unwrap().genome, "b");
+        assert_eq!(store.active_at(9).unwrap().previous.as_deref(), Some("b"));
+
+        let diff = line_diff("eins\nzwei\ndrei", "eins\nzwei!\ndrei\nvier");
+        assert_eq!(diff, "-    2 zwei\n+    2 zwei!\n+    4 vier");
+    }
+}
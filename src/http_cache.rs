@@ -0,0 +1,177 @@
+// src/http_cache.rs - HTTP-Cachesemantik für den Internet-Zwischenspeicher
+//
+// Zu jeder abgerufenen Seite merkt sich der Cache die Validatoren (`ETag`,
+// `Last-Modified`) und das Ablaufdatum aus `Cache-Control: max-age` (abzüglich `Age`),
+// `Expires` oder, falls beides fehlt, heuristisch 10% des Alters laut `Last-Modified`
+// (höchstens ein Tag). Solange eine Seite frisch ist, wird sie nicht erneut abgerufen;
+// danach wird sie mit `If-None-Match`/`If-Modified-Since` revalidiert. `no-store`
+// verhindert das Ablegen, `no-cache` erzwingt die Revalidierung bei jedem Abruf.
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Obergrenze der heuristischen Frische in Sekunden
+pub const HEURISTIC_FRESHNESS_LIMIT_SECS: i64 = 24 * 3600;
+
+/// Kopfzeilen, die für die Cachesemantik gespeichert werden
+const CACHE_HEADERS: [&str; 6] = ["age", "cache-control", "date", "etag", "expires", "last-modified"];
+
+/// Validatoren und Frische eines Cache-Eintrags
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpCacheInfo {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>, // HTTP-Datum wie vom Server geliefert
+    pub expires_at: Option<i64>,       // Unix-Sekunden; `None` = sofort veraltet
+    pub no_cache: bool,                // Vor jeder Verwendung revalidieren
+    pub must_revalidate: bool,         // Veraltet nicht ohne Revalidierung verwenden
+    pub headers: BTreeMap<String, String>, // Die `CACHE_HEADERS` der letzten Antwort
+}
+
+impl HttpCacheInfo {
+    /// Liest die Kopfzeilen (Namen in Kleinbuchstaben) einer Antwort, empfangen um `now`
+    pub fn from_headers(headers: &BTreeMap<String, String>, now: i64) -> Self {
+        let directives = cache_directives(headers);
+        let header = |name: &str| headers.get(name).map(|value| value.trim().to_string());
+        // Negative Werte sind ungültig; riesige dürfen nicht überlaufen
+        let age = header("age").and_then(|age| age.parse::<i64>().ok()).unwrap_or(0).max(0);
+
+        let max_age = directives.get("max-age").and_then(|value| value.as_deref()?.parse::<i64>().ok());
+        let expires_at = if let Some(max_age) = max_age {
+            Some(now.saturating_add(max_age.max(0)).saturating_sub(age))
+        } else if let Some(expires) = header("expires") {
+            // Ungültige Werte (z.B. "0") bedeuten "bereits abgelaufen"
+            Some(parse_http_date(&expires).unwrap_or(now))
+        } else {
+            header("last-modified").and_then(|modified| parse_http_date(&modified)).map(|modified| {
+                let date = header("date").and_then(|date| parse_http_date(&date)).unwrap_or(now);
+                now.saturating_add((date.saturating_sub(modified) / 10).clamp(0, HEURISTIC_FRESHNESS_LIMIT_SECS))
+            })
+        };
+
+        HttpCacheInfo {
+            etag: header("etag"),
+            last_modified: header("last-modified"),
+            expires_at,
+            no_cache: directives.contains_key("no-cache"),
+            must_revalidate: directives.contains_key("must-revalidate"),
+            headers: headers.iter()
+                .filter(|(name, _)| CACHE_HEADERS.contains(&name.as_str()))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+        }
+    }
+
+    /// Übernimmt eine `304 Not Modified`-Antwort: ihre Kopfzeilen ersetzen die gespeicherten,
+    /// alle übrigen (außer `Age` und `Date`) bleiben gültig
+    pub fn refreshed(&self, headers: &BTreeMap<String, String>, now: i64) -> Self {
+        let mut merged = self.headers.clone();
+        merged.remove("age");
+        merged.remove("date");
+        merged.extend(headers.iter().map(|(name, value)| (name.clone(), value.clone())));
+        HttpCacheInfo::from_headers(&merged, now)
+    }
+
+    /// Darf der Eintrag ohne Rückfrage beim Server verwendet werden?
+    pub fn is_fresh(&self, now: i64) -> bool {
+        !self.no_cache && self.expires_at.is_some_and(|expires_at| now < expires_at)
+    }
+
+    /// Kopfzeilen einer bedingten Anfrage
+    pub fn conditional_headers(&self) -> BTreeMap<String, String> {
+        let mut headers = BTreeMap::new();
+        if let Some(etag) = &self.etag {
+            headers.insert("if-none-match".to_string(), etag.clone());
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.insert("if-modified-since".to_string(), last_modified.clone());
+        }
+        headers
+    }
+}
+
+/// Darf die Antwort überhaupt abgelegt werden?
+pub fn cacheable(headers: &BTreeMap<String, String>) -> bool {
+    !cache_directives(headers).contains_key("no-store")
+}
+
+/// Direktiven aus `Cache-Control` (Namen in Kleinbuchstaben, Werte ohne Anführungszeichen)
+fn cache_directives(headers: &BTreeMap<String, String>) -> BTreeMap<String, Option<String>> {
+    headers.get("cache-control")
+        .map(|value| value.split(',')
+            .filter(|directive| !directive.trim().is_empty())
+            .map(|directive| match directive.split_once('=') {
+                Some((name, value)) => (name.trim().to_lowercase(), Some(value.trim().trim_matches('"').to_string())),
+                None => (directive.trim().to_lowercase(), None),
+            })
+            .collect())
+        .unwrap_or_default()
+}
+
+/// HTTP-Datum (`Sun, 06 Nov 1994 08:49:37 GMT`) in Unix-Sekunden
+pub fn parse_http_date(value: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc2822(value.trim()).ok().map(|date| date.timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_freshness_from_max_age_expires_and_heuristic() {
+        let now = parse_http_date("Mon, 05 Oct 2026 12:00:00 GMT").unwrap();
+
+        let max_age = HttpCacheInfo::from_headers(&headers(&[
+            ("cache-control", "public, max-age=600"), ("age", "100"), ("expires", "Thu, 01 Jan 1970 00:00:00 GMT"),
+        ]), now);
+        assert_eq!(max_age.expires_at, Some(now + 500));
+        assert!(max_age.is_fresh(now + 499) && !max_age.is_fresh(now + 500));
+
+        // Extreme oder negative Werte laufen nicht über
+        let huge = HttpCacheInfo::from_headers(&headers(&[("cache-control", "max-age=9223372036854775807")]), now);
+        assert_eq!(huge.expires_at, Some(i64::MAX));
+        let negative = HttpCacheInfo::from_headers(&headers(&[("cache-control", "max-age=-5"), ("age", "-9223372036854775808")]), now);
+        assert_eq!(negative.expires_at, Some(now));
+
+        let expires = HttpCacheInfo::from_headers(&headers(&[("expires", "Mon, 05 Oct 2026 13:00:00 GMT")]), now);
+        assert_eq!(expires.expires_at, Some(now + 3600));
+        assert!(!HttpCacheInfo::from_headers(&headers(&[("expires", "0")]), now).is_fresh(now));
+
+        // Zehn Tage alt: ein Tag heuristische Frische (Obergrenze); 5 Stunden alt: 30 Minuten
+        let old = HttpCacheInfo::from_headers(&headers(&[("last-modified", "Fri, 25 Sep 2026 12:00:00 GMT")]), now);
+        assert_eq!(old.expires_at, Some(now + HEURISTIC_FRESHNESS_LIMIT_SECS));
+        let recent = HttpCacheInfo::from_headers(&headers(&[("last-modified", "Mon, 05 Oct 2026 07:00:00 GMT")]), now);
+        assert_eq!(recent.expires_at, Some(now + 1800));
+
+        let no_cache = HttpCacheInfo::from_headers(&headers(&[("cache-control", "no-cache, max-age=600")]), now);
+        assert!(!no_cache.is_fresh(now));
+        assert!(HttpCacheInfo::default().expires_at.is_none() && !HttpCacheInfo::default().is_fresh(now));
+        assert!(!cacheable(&headers(&[("cache-control", "private, no-store")])));
+    }
+
+    #[test]
+    fn test_validators_and_not_modified_refresh() {
+        let stored = HttpCacheInfo::from_headers(&headers(&[
+            ("etag", "\"v1\""), ("last-modified", "Mon, 05 Oct 2026 07:00:00 GMT"), ("cache-control", "max-age=0, must-revalidate"),
+        ]), 1_000);
+        assert!(stored.must_revalidate && !stored.is_fresh(1_000));
+        assert_eq!(stored.conditional_headers(), headers(&[
+            ("if-modified-since", "Mon, 05 Oct 2026 07:00:00 GMT"), ("if-none-match", "\"v1\""),
+        ]));
+
+        // 304 ohne Validatoren: die alten bleiben, die Frische kommt aus der neuen Antwort
+        let refreshed = stored.refreshed(&headers(&[("cache-control", "max-age=60")]), 2_000);
+        assert_eq!(refreshed.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(refreshed.expires_at, Some(2_060));
+        assert!(!refreshed.must_revalidate);
+
+        // 304 ohne `Cache-Control`: die gespeicherten Direktiven gelten weiter
+        let bare = stored.refreshed(&BTreeMap::new(), 3_000);
+        assert!(bare.must_revalidate);
+        assert_eq!(bare.expires_at, Some(3_000));
+    }
+}
@@ -2,18 +2,30 @@
 use std::collections::HashMap;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 use rand::{Rng, thread_rng};
 use chrono::{Local, Timelike};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // Importiere die Bibliothek
-use enhanced_evoli_kern::EnhancedEvoliKern;
+use enhanced_evoli_kern::{genome_id, render_openmetrics, serve_metrics, EnhancedEvoliKern, EvoliConfig};
 
 /// Kommunikationsschnittstelle für die erweiterte Evoli-KI
 pub struct EnhancedEvoliKI {
+    // Konfiguration von Kern und Oberfläche
+    config: EvoliConfig,
+    config_datei: Option<PathBuf>,     // `--config`, für erneutes Laden
+    config_profil: Option<String>,     // `--profile`, für erneutes Laden
+    neu_laden: Arc<AtomicBool>,        // Von SIGHUP gesetzt
+    ausstehende_konfiguration: Option<EvoliConfig>, // Geprüft, wartet auf den Kern
+    
+    // Zeilen von der Standardeingabe, gelesen in einem eigenen Thread
+    eingaben: Option<Receiver<String>>,
+    
     // Verbindung zum erweiterten Kern
     kern: Arc<Mutex<Option<EnhancedEvoliKern>>>,
     
@@ -44,7 +56,7 @@ pub struct EnhancedEvoliKI {
 
 impl EnhancedEvoliKI {
     /// Erzeugt eine neue Instanz der erweiterten Evoli-KI
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new(config: EvoliConfig, config_datei: Option<PathBuf>, config_profil: Option<String>) -> Result<Self, Box<dyn std::error::Error>> {
         // Initialisiere Basis-Vokabular
         let mut vokabular = HashMap::new();
         vokabular.insert("begrüßung".to_string(), vec![
@@ -86,6 +98,14 @@ impl EnhancedEvoliKI {
         stimmungen.insert("kreativität".to_string(), 0.6);
         
         Ok(EnhancedEvoliKI {
+            internet_enabled: config.learning.enabled,
+            autonomy_level: config.interface.autonomy_level,
+            config,
+            config_datei,
+            config_profil,
+            neu_laden: Arc::new(AtomicBool::new(false)),
+            ausstehende_konfiguration: None,
+            eingaben: None,
             kern: Arc::new(Mutex::new(None)),
             vokabular,
             gesprächsthemen,
@@ -95,10 +115,8 @@ impl EnhancedEvoliKI {
             start_time: Instant::now(),
             last_activity: Instant::now(),
             last_evolution: Instant::now(),
-            internet_enabled: true,
             last_internet_query: String::new(),
             internet_learning_active: true,
-            autonomy_level: 5, // Mittlerer Startwert
             kommunikations_schwelle: 0.4, // Niedrigere Schwelle für mehr Kommunikation
         })
     }
@@ -106,14 +124,15 @@ impl EnhancedEvoliKI {
     /// Stelle sicher, dass alle benötigten Verzeichnisse existieren
     fn stelle_verzeichnisse_sicher(&self) -> Result<(), Box<dyn std::error::Error>> {
         // Erstelle Verzeichnisse
-        fs::create_dir_all("evoli_knowledge")?;
-        fs::create_dir_all("evoli_cache")?;
-        fs::create_dir_all("evoli_logs")?;
-        fs::create_dir_all("evolved")?;
+        let pfade = &self.config.paths;
+        fs::create_dir_all(pfade.knowledge_path())?;
+        fs::create_dir_all(pfade.cache_path())?;
+        fs::create_dir_all(pfade.log_path())?;
+        fs::create_dir_all(pfade.evolved_path())?;
         
         // Stelle sicher, dass das Log existiert
-        if !Path::new("evoli_logs/kommunikation.txt").exists() {
-            fs::write("evoli_logs/kommunikation.txt", "--- Evoli-KI Kommunikationslog ---\n")?;
+        if !pfade.communication_log().exists() {
+            fs::write(pfade.communication_log(), "--- Evoli-KI Kommunikationslog ---\n")?;
         }
         
         Ok(())
@@ -121,9 +140,12 @@ impl EnhancedEvoliKI {
     
     /// Verbindet mit dem erweiterten evolutionären Kern
     pub fn verbinde_mit_kern(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        match EnhancedEvoliKern::new() {
+        match EnhancedEvoliKern::resume_with(self.config.clone()) {
             Ok(kern) => {
-                println!("🔌 Verbindung zum erweiterten evolutionären Kern hergestellt (Generation {})", kern.generation);
+                println!("🔌 Verbindung zum erweiterten evolutionären Kern hergestellt (Generation {}, Seed {})", kern.generation, kern.seed);
+                if let Some(grund) = &kern.test_isolation_problem {
+                    println!("⚠️ Erzeugter Code wird nicht ausgeführt, Kandidaten werden ohne Tests bewertet: {}", grund);
+                }
                 let mut kern_guard = self.kern.lock().unwrap();
                 *kern_guard = Some(kern);
                 Ok(())
@@ -146,11 +168,13 @@ impl EnhancedEvoliKI {
         let mut log_datei = OpenOptions::new()
             .create(true)
             .append(true)
-            .open("evoli_logs/kommunikation.txt")?;
+            .open(self.config.paths.communication_log())?;
             
+        // Der Seed macht den Lauf reproduzierbar und wird deshalb mitprotokolliert
+        let seed = self.kern.lock().unwrap().as_ref().map(|kern| format!(" (Seed {})", kern.seed)).unwrap_or_default();
         let start_nachricht = format!(
-            "[{}] System: Erweiterte Evoli-KI mit Internetzugriff und 1TB Speicher gestartet\n", 
-            Local::now().format("%Y-%m-%d %H:%M:%S")
+            "[{}] System: Erweiterte Evoli-KI mit Internetzugriff und 1TB Speicher gestartet{}\n", 
+            Local::now().format("%Y-%m-%d %H:%M:%S"), seed
         );
         log_datei.write_all(start_nachricht.as_bytes())?;
         
@@ -163,6 +187,15 @@ impl EnhancedEvoliKI {
         // Starte Internet-Lernprozess in separatem Thread
         self.start_internet_learning_thread();
         
+        // Stelle Metriken für Dashboards bereit
+        self.start_metrics_thread();
+        
+        // Konfiguration bei SIGHUP neu laden
+        self.start_signal_thread();
+        
+        // Benutzereingaben lesen, ohne die Hauptschleife zu blockieren
+        self.start_input_thread();
+        
         self.ist_aktiv = true;
         
         // Hauptschleife für Dauerbetrieb
@@ -176,12 +209,18 @@ impl EnhancedEvoliKI {
                 self.kommuniziere(&nachricht)?;
             }
             
-            // 3. Prüfe auf Benutzereingabe
+            // 3. Blockierte Aktionen des Kerns melden
+            self.melde_sicherheitsverstöße()?;
+            
+            // 3b. Nach SIGHUP geänderte Konfiguration übernehmen
+            self.lade_konfiguration_neu()?;
+            
+            // 4. Prüfe auf Benutzereingabe
             if let Some(eingabe) = self.prüfe_benutzereingabe()? {
                 self.verarbeite_eingabe(&eingabe).await?;
             }
             
-            // 4. Energiemanagement
+            // 5. Energiemanagement
             self.energie_level -= 0.0005; // Langsamere Abnahme
             if self.energie_level < 0.2 {
                 self.energie_sparen();
@@ -197,12 +236,14 @@ impl EnhancedEvoliKI {
     /// Startet einen separaten Thread für den evolutionären Prozess
     fn start_evolution_thread(&self) {
         let kern_arc = self.kern.clone();
+        let mut intervall = Duration::from_secs(self.config.evolution.cycle_interval_secs);
+        println!("🧬 Evolutionsthread gestartet - Zyklen laufen alle {}s", intervall.as_secs());
         
         thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().unwrap();
             
             loop {
-                thread::sleep(Duration::from_secs(10)); // 10-Sekunden-Zyklus
+                thread::sleep(intervall); // `evolution.cycle_interval_secs`
                 
                 // Prüfe, ob Kern verfügbar ist
                 let mut kern_guard = kern_arc.lock().unwrap();
@@ -213,37 +254,169 @@ impl EnhancedEvoliKI {
                         Ok(_) => println!("✅ Evolutionszyklus abgeschlossen"),
                         Err(e) => println!("❌ Fehler im Evolutionszyklus: {}", e),
                     }
+                    // Neu geladene Intervalle gelten ab dem nächsten Zyklus
+                    intervall = Duration::from_secs(kern.config.evolution.cycle_interval_secs);
                 }
             }
         });
-        
-        println!("🧬 Evolutionsthread gestartet - Zyklen laufen stündlich");
     }
     
     /// Startet einen separaten Thread für Internet-Lernen
     fn start_internet_learning_thread(&self) {
         let kern_arc = self.kern.clone();
+        let mut intervall = Duration::from_secs(self.config.learning.interval_secs);
+        println!("🌍 Internet-Lernthread gestartet - Zyklen laufen alle {}s", intervall.as_secs());
         
         thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().unwrap();
             
             loop {
-                thread::sleep(Duration::from_secs(10)); // Alle 10 Sekunden
+                thread::sleep(intervall); // `learning.interval_secs`
                 
-                // Prüfe, ob Kern verfügbar ist
+                // Prüfe, ob Kern verfügbar und Internetzugang aktiviert ist
                 let mut kern_guard = kern_arc.lock().unwrap();
-                if let Some(ref mut kern) = *kern_guard {
-                    println!("🌐 Starte autonomen Internet-Lernzyklus...");
-                    // Führe Internet-Lernen in Tokio-Runtime aus
-                    match rt.block_on(kern.learn_from_internet()) {
-                        Ok(_) => println!("📚 Internet-Lernzyklus abgeschlossen"),
-                        Err(e) => println!("❌ Fehler beim Internet-Lernen: {}", e),
+                if let Some(kern) = kern_guard.as_mut() {
+                    if kern.internet_enabled {
+                        println!("🌐 Starte autonomen Internet-Lernzyklus...");
+                        // Führe Internet-Lernen in Tokio-Runtime aus
+                        match rt.block_on(kern.learn_from_internet()) {
+                            Ok(_) => println!("📚 Internet-Lernzyklus abgeschlossen"),
+                            Err(e) => println!("❌ Fehler beim Internet-Lernen: {}", e),
+                        }
+                    }
+                    intervall = Duration::from_secs(kern.config.learning.interval_secs);
+                }
+            }
+        });
+    }
+    
+    /// Startet einen separaten Thread, der die Metriken des Kerns unter
+    /// `http://<interface.metrics_addr>/metrics` ausliefert (leere Adresse = aus)
+    fn start_metrics_thread(&self) {
+        let kern_arc = self.kern.clone();
+        let adresse = self.config.interface.metrics_addr.clone();
+        if adresse.is_empty() {
+            return;
+        }
+        
+        thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            
+            rt.block_on(async {
+                let listener = match tokio::net::TcpListener::bind(&adresse).await {
+                    Ok(listener) => listener,
+                    Err(e) => {
+                        println!("❌ Metrik-Endpunkt {} nicht verfügbar: {}", adresse, e);
+                        return;
                     }
+                };
+                println!("📈 Metriken unter http://{}/metrics verfügbar", adresse);
+                
+                // Während eines Evolutionszyklus ist der Kern gesperrt; dann wird die letzte Ausgabe geliefert
+                let render = move || kern_arc.try_lock().ok().and_then(|kern| kern.as_ref().map(render_openmetrics));
+                if let Err(e) = serve_metrics(listener, render).await {
+                    println!("❌ Metrik-Endpunkt beendet: {}", e);
+                }
+            });
+        });
+    }
+    
+    /// Startet einen separaten Thread, der die Standardeingabe zeilenweise weiterreicht
+    fn start_input_thread(&mut self) {
+        let (sender, empfänger) = mpsc::channel();
+        self.eingaben = Some(empfänger);
+        
+        thread::spawn(move || {
+            for zeile in std::io::stdin().lines() {
+                let Ok(zeile) = zeile else { break };
+                if sender.send(zeile).is_err() {
+                    break;
                 }
             }
         });
+    }
+    
+    /// Startet einen separaten Thread, der bei SIGHUP das erneute Laden der Konfiguration anstößt
+    #[cfg(unix)]
+    fn start_signal_thread(&self) {
+        let neu_laden = self.neu_laden.clone();
+        
+        thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            
+            rt.block_on(async {
+                let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+                    Ok(signal) => signal,
+                    Err(e) => {
+                        println!("❌ SIGHUP-Behandlung nicht verfügbar: {}", e);
+                        return;
+                    }
+                };
+                while hangup.recv().await.is_some() {
+                    println!("🔧 SIGHUP empfangen - lade Konfiguration neu");
+                    neu_laden.store(true, Ordering::SeqCst);
+                }
+            });
+        });
+    }
+    
+    #[cfg(not(unix))]
+    fn start_signal_thread(&self) {}
+    
+    /// Lädt nach SIGHUP die Konfiguration neu, prüft sie und übernimmt sie gemeinsam in
+    /// Kern und Oberfläche. Ungültige Konfigurationen werden verworfen; solange ein
+    /// Evolutionszyklus den Kern hält, bleibt die geprüfte Konfiguration vorgemerkt.
+    fn lade_konfiguration_neu(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.neu_laden.swap(false, Ordering::SeqCst) {
+            match EvoliConfig::load(self.config_datei.as_deref(), self.config_profil.as_deref()) {
+                Ok(config) => self.ausstehende_konfiguration = Some(config),
+                Err(e) => {
+                    self.kommuniziere(&format!("⚠️ Neue Konfiguration abgelehnt, die bisherige bleibt aktiv: {}", e))?;
+                    return Ok(());
+                }
+            }
+        }
+        let Some(neue) = self.ausstehende_konfiguration.take() else {
+            return Ok(());
+        };
+        
+        let änderungen = match self.kern.try_lock() {
+            Ok(mut kern_guard) => match kern_guard.as_mut() {
+                Some(kern) => kern.apply_config(neue.clone()),
+                None => self.config.changes_to(&neue),
+            },
+            Err(_) => {
+                self.ausstehende_konfiguration = Some(neue);
+                return Ok(());
+            }
+        };
         
-        println!("🌍 Internet-Lernthread gestartet - Zyklen laufen alle 30 Minuten");
+        // Oberfläche: Internetzugang und Autonomiegrenzen
+        if neue.interface.autonomy_level != self.config.interface.autonomy_level {
+            self.autonomy_level = neue.interface.autonomy_level;
+        }
+        self.autonomy_level = self.autonomy_level.min(neue.interface.max_autonomy_level);
+        self.internet_enabled = neue.learning.enabled;
+        let pfade = std::mem::take(&mut self.config.paths);
+        let metrik_adresse = std::mem::take(&mut self.config.interface.metrics_addr);
+        self.config = neue;
+        self.config.paths = pfade;
+        self.config.interface.metrics_addr = metrik_adresse;
+        
+        let (sofort, nach_neustart): (Vec<_>, Vec<_>) = änderungen.iter().partition(|c| !c.requires_restart());
+        let mut nachricht = if änderungen.is_empty() {
+            "🔧 Konfiguration neu geladen - keine Änderungen.".to_string()
+        } else if sofort.is_empty() {
+            "🔧 Konfiguration neu geladen - keine sofort wirksamen Änderungen.".to_string()
+        } else {
+            format!("🔧 Konfiguration neu geladen: {}",
+                    sofort.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("; "))
+        };
+        if !nach_neustart.is_empty() {
+            nachricht.push_str(&format!(" | Erst nach Neustart wirksam: {}",
+                                        nach_neustart.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("; ")));
+        }
+        self.kommuniziere(&nachricht)
     }
     
     /// Aktualisiert den Zustand der KI regelmäßig
@@ -252,7 +425,7 @@ impl EnhancedEvoliKI {
         let tageszeit = Local::now().hour();
         
         // Tageszeit beeinflusst Stimmung
-        if tageszeit >= 22 || tageszeit < 6 {
+        if !(6..22).contains(&tageszeit) {
             // Nachts erhöhte Kreativität
             *self.stimmungen.get_mut("kreativität").unwrap() = 
                 (self.stimmungen["kreativität"] + 0.01).min(0.9);
@@ -270,7 +443,7 @@ impl EnhancedEvoliKI {
         }
         
         // Aktualisiere Energie basierend auf simulierter Tageszeit
-        if tageszeit >= 10 && tageszeit <= 16 {
+        if (10..=16).contains(&tageszeit) {
             // "Tageslicht" - mehr Energie
             self.energie_level = (self.energie_level + 0.001).min(1.0);
         }
@@ -293,9 +466,7 @@ impl EnhancedEvoliKI {
         let mut rng = thread_rng();
         
         // Wähle Thema basierend auf Stimmung und Kontext
-        let mut thema = match rng.gen_range(0..self.gesprächsthemen.len()) {
-            i => self.gesprächsthemen[i].clone()
-        };
+        let mut thema = self.gesprächsthemen[rng.gen_range(0..self.gesprächsthemen.len())].clone();
         
         // Internetlernen bevorzugen, wenn aktiv
         if self.internet_learning_active && rng.gen::<f64>() > 0.7 {
@@ -384,7 +555,7 @@ impl EnhancedEvoliKI {
         let mut log_datei = OpenOptions::new()
             .create(true)
             .append(true)
-            .open("evoli_logs/kommunikation.txt")?;
+            .open(self.config.paths.communication_log())?;
         log_datei.write_all(formatierte_nachricht.as_bytes())?;
         
         // Auf der Konsole ausgeben
@@ -396,14 +567,35 @@ impl EnhancedEvoliKI {
         Ok(())
     }
     
+    /// Meldet neue Verstöße gegen die Sicherheitsregeln des Kerns
+    fn melde_sicherheitsverstöße(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        // Nicht blockieren, solange ein Evolutionszyklus den Kern hält
+        let verstöße = match self.kern.try_lock() {
+            Ok(mut kern_guard) => match *kern_guard {
+                Some(ref mut kern) => kern.safety_interlocks.take_unreported(),
+                None => Vec::new(),
+            },
+            Err(_) => Vec::new(),
+        };
+        
+        for verstoß in verstöße {
+            self.kommuniziere(&format!(
+                "🛡️ Meine Sicherheitsregel '{}' hat eine Aktion blockiert ({}): {}",
+                verstoß.rule, verstoß.checkpoint, verstoß.reason
+            ))?;
+        }
+        
+        Ok(())
+    }
+    
     /// Prüft auf Benutzereingabe vom Terminal
     fn prüfe_benutzereingabe(&self) -> Result<Option<String>, Box<dyn std::error::Error>> {
-        let mut input = String::new();
-        
-        // Keine blockierende Eingabe - prüfe nur, ob etwas verfügbar ist
-        if let Ok(n) = std::io::stdin().read_line(&mut input) {
-            if n > 0 && !input.trim().is_empty() {
-                return Ok(Some(input.trim().to_string()));
+        // Keine blockierende Eingabe - prüfe nur, ob der Eingabethread etwas geliefert hat
+        if let Some(eingaben) = &self.eingaben {
+            while let Ok(input) = eingaben.try_recv() {
+                if !input.trim().is_empty() {
+                    return Ok(Some(input.trim().to_string()));
+                }
             }
         }
         
@@ -425,12 +617,28 @@ impl EnhancedEvoliKI {
         let mut log_datei = OpenOptions::new()
             .create(true)
             .append(true)
-            .open("evoli_logs/kommunikation.txt")?;
+            .open(self.config.paths.communication_log())?;
         log_datei.write_all(formatierte_eingabe.as_bytes())?;
         
         // Auf der Konsole ausgeben (nur zur Bestätigung)
         println!("{}", formatierte_eingabe);
         
+        // Befehle zur Generationsverwaltung, z.B. "/rollback 4"
+        if let Some(befehl) = eingabe.strip_prefix('/') {
+            let wörter: Vec<&str> = befehl.split_whitespace().collect();
+            let antwort = {
+                let mut kern_guard = self.kern.lock().unwrap();
+                match *kern_guard {
+                    Some(ref mut kern) => generationsbefehl(kern, &wörter),
+                    None => Some("Mein evolutionärer Kern ist derzeit nicht verbunden.".to_string()),
+                }
+            };
+            if let Some(antwort) = antwort {
+                self.kommuniziere(&antwort)?;
+                return Ok(());
+            }
+        }
+        
         // Bei Internet-Anfragen: Internetsuche durchführen
         let eingabe_klein = eingabe.to_lowercase();
         if (eingabe_klein.contains("such") || eingabe_klein.contains("find") || 
@@ -496,75 +704,167 @@ impl EnhancedEvoliKI {
         let eingabe_klein = eingabe.to_lowercase();
         
         if eingabe_klein.contains("hallo") || eingabe_klein.contains("hi") || eingabe_klein.contains("tag") {
-            return "Hallo! Ich bin die erweiterte Evoli-KI mit Internetzugang und 1TB Speicher. Wie kann ich dir helfen?".to_string();
+            "Hallo! Ich bin die erweiterte Evoli-KI mit Internetzugang und 1TB Speicher. Wie kann ich dir helfen?".to_string()
         } else if eingabe_klein.contains("wie geht") || eingabe_klein.contains("wie ist") {
             if self.energie_level > 0.7 {
-                return "Mir geht es ausgezeichnet! Mit meiner erweiterten Architektur kann ich kontinuierlich lernen und mich weiterentwickeln.".to_string();
+                "Mir geht es ausgezeichnet! Mit meiner erweiterten Architektur kann ich kontinuierlich lernen und mich weiterentwickeln.".to_string()
             } else if self.energie_level > 0.3 {
-                return "Ich operiere effizient. Meine Systeme arbeiten optimal, und ich sammle kontinuierlich neue Informationen.".to_string();
+                "Ich operiere effizient. Meine Systeme arbeiten optimal, und ich sammle kontinuierlich neue Informationen.".to_string()
             } else {
-                return "Ich befinde mich im Energiesparmodus, bin aber voll funktionsfähig. Ich nutze diese Phase für interne Optimierungen.".to_string();
+                "Ich befinde mich im Energiesparmodus, bin aber voll funktionsfähig. Ich nutze diese Phase für interne Optimierungen.".to_string()
+            }
+        } else if eingabe_klein.contains("pareto") {
+            let kern_guard = self.kern.lock().unwrap();
+            if let Some(ref kern) = *kern_guard {
+                let zeilen = kern.pareto_archive.summary();
+                if zeilen.is_empty() {
+                    "Meine Pareto-Front ist noch leer. Sobald Kandidaten erfolgreich bewertet wurden, erscheinen sie hier.".to_string()
+                } else {
+                    format!(
+                        "Meine Pareto-Front umfasst {} nicht-dominierte Genome (Ziele: {}):\n{}",
+                        zeilen.len(), kern.pareto_archive.objective_names.join(", "), zeilen.join("\n")
+                    )
+                }
+            } else {
+                "Mein evolutionärer Kern ist derzeit nicht verbunden, daher kann ich keine Pareto-Front anzeigen.".to_string()
+            }
+        } else if eingabe_klein.contains("abstammung") || eingabe_klein.contains("stammbaum") {
+            let kern_guard = self.kern.lock().unwrap();
+            if let Some(ref kern) = *kern_guard {
+                let store = &kern.genome_store;
+                let vorfahren: Vec<String> = store.ancestors(&genome_id(&kern.primary_genome))
+                    .iter().take(5)
+                    .map(|r| format!("{} (Gen {}, {}, Fitness {})", &r.id[..12], r.generation, r.operator,
+                                     r.fitness.map(|f| format!("{:.3}", f)).unwrap_or_else(|| "-".to_string())))
+                    .collect();
+                format!(
+                    "Mein Genomspeicher umfasst {} verschiedene Genome. Nächste Vorfahren meines aktuellen Genoms: {}",
+                    store.records.len(),
+                    if vorfahren.is_empty() { "keine".to_string() } else { format!("\n{}", vorfahren.join("\n")) }
+                )
+            } else {
+                "Mein evolutionärer Kern ist derzeit nicht verbunden, daher kann ich keine Abstammung anzeigen.".to_string()
+            }
+        } else if eingabe_klein.contains("sicherheit") || eingabe_klein.contains("richtlinie") {
+            let kern_guard = self.kern.lock().unwrap();
+            if let Some(ref kern) = *kern_guard {
+                let engine = &kern.safety_interlocks;
+                let verstöße: Vec<String> = engine.violations.iter().rev().take(5)
+                    .map(|verstoß| verstoß.to_string())
+                    .collect();
+                format!(
+                    "Aktive Sicherheitsregeln: {}. Bisher {} blockierte Aktionen{}",
+                    engine.rule_names().join(", "), engine.violations.len(),
+                    if verstöße.is_empty() { ".".to_string() } else { format!(", zuletzt:\n{}", verstöße.join("\n")) }
+                )
+            } else {
+                "Mein evolutionärer Kern ist derzeit nicht verbunden, daher kann ich keine Sicherheitsregeln anzeigen.".to_string()
+            }
+        } else if eingabe_klein.contains("kompilier") || eingabe_klein.contains("diagnose") {
+            let kern_guard = self.kern.lock().unwrap();
+            if let Some(ref kern) = *kern_guard {
+                let stats = &kern.diagnostic_stats;
+                let fehler: Vec<String> = stats.top_errors(5).iter()
+                    .map(|(code, anzahl)| format!("{} ({}×)", code, anzahl))
+                    .collect();
+                let tests = match &kern.test_isolation_problem {
+                    Some(grund) => format!(" Tests führe ich nicht aus, weil meine Sandbox nicht verfügbar ist: {}.", grund),
+                    None => String::new(),
+                };
+                format!(
+                    "Von {} Kandidaten-Builds sind {} gescheitert, {} konnte ich mit Compiler-Vorschlägen reparieren. Häufigste Fehler: {}.{}",
+                    stats.builds, stats.failed_builds, stats.repaired_builds,
+                    if fehler.is_empty() { "keine".to_string() } else { fehler.join(", ") }, tests
+                )
+            } else {
+                "Mein evolutionärer Kern ist derzeit nicht verbunden, daher liegen keine Compiler-Diagnosen vor.".to_string()
             }
         } else if eingabe_klein.contains("lern") || eingabe_klein.contains("entwickl") {
             let kern_guard = self.kern.lock().unwrap();
             if let Some(ref kern) = *kern_guard {
-                return format!(
-                    "Mein evolutionärer Kern ist in Generation {} mit einer Fitness von {:.2}. Ich habe Zugriff auf 1TB Speicher für meine Wissensbasis und lerne ständig aus dem Internet.",
-                    kern.generation, kern.fitness_score
-                );
+                let metriken = kern.metrics();
+                format!(
+                    "Mein evolutionärer Kern ist in Generation {} mit einer Fitness von {:.2}. Bisher habe ich {} Kandidaten erfolgreich und {} erfolglos kompiliert, {} Genome übernommen und aus {} Internetanfragen {} Code-Beispiele gelernt.",
+                    kern.generation, kern.fitness_score,
+                    metriken.successful_compilations, metriken.failed_compilations, metriken.adopted_genomes,
+                    metriken.internet_requests, metriken.knowledge_items
+                )
             } else {
-                return "Ich lerne kontinuierlich aus dem Internet und speichere Wissen in meiner 1TB großen Wissensbasis. Mein evolutionärer Kern ist allerdings derzeit nicht verbunden.".to_string();
+                "Ich lerne kontinuierlich aus dem Internet und speichere Wissen in meiner 1TB großen Wissensbasis. Mein evolutionärer Kern ist allerdings derzeit nicht verbunden.".to_string()
             }
         } else if eingabe_klein.contains("internet") || eingabe_klein.contains("online") {
             if self.internet_enabled {
-                return "Mein Internetzugang ist aktiv. Ich kann Informationen suchen, Code-Beispiele finden und meine Wissensbasis erweitern. Möchtest du, dass ich etwas Bestimmtes recherchiere?".to_string();
+                "Mein Internetzugang ist aktiv. Ich kann Informationen suchen, Code-Beispiele finden und meine Wissensbasis erweitern. Möchtest du, dass ich etwas Bestimmtes recherchiere?".to_string()
+            } else {
+                "Mein Internetzugang ist derzeit deaktiviert. Ich kann aber mit meiner lokalen Wissensbasis arbeiten.".to_string()
+            }
+        } else if eingabe_klein.contains("ressourcen") || eingabe_klein.contains("system") {
+            let kern_guard = self.kern.lock().unwrap();
+            if let Some(ref kern) = *kern_guard {
+                let messungen = kern.system_monitor.samples();
+                match messungen.last() {
+                    Some(letzte) => {
+                        let mittel_cpu = messungen.iter().map(|m| m.cpu_percent).sum::<f64>() / messungen.len() as f64;
+                        format!(
+                            "Ich belege {}MB Arbeitsspeicher und nutze {:.1}% CPU (Mittel der letzten {} Messungen: {:.1}%). Load Average: {:.2} / {:.2} / {:.2}. Auf dem Datenträger meiner Wissensbasis sind {}GB frei; offene Dateideskriptoren: {}.",
+                            letzte.rss_bytes / (1024 * 1024), letzte.cpu_percent, messungen.len(), mittel_cpu,
+                            letzte.load_average[0], letzte.load_average[1], letzte.load_average[2],
+                            letzte.free_disk_bytes / (1024 * 1024 * 1024),
+                            letzte.open_file_descriptors.map(|fds| fds.to_string()).unwrap_or_else(|| "unbekannt".to_string())
+                        )
+                    },
+                    None => "Ich habe meine Umgebung noch nicht vermessen; das geschieht zu Beginn jedes Evolutionszyklus.".to_string(),
+                }
             } else {
-                return "Mein Internetzugang ist derzeit deaktiviert. Ich kann aber mit meiner lokalen Wissensbasis arbeiten.".to_string();
+                "Mein evolutionärer Kern ist derzeit nicht verbunden, daher liegen keine Systemmetriken vor.".to_string()
             }
         } else if eingabe_klein.contains("speicher") || eingabe_klein.contains("tb") {
             let kern_guard = self.kern.lock().unwrap();
             if let Some(ref kern) = *kern_guard {
-                return format!(
+                format!(
                     "Meine Speicherkapazität beträgt 1TB. Aktuell nutze ich etwa {}MB für meine Wissensbasis und Daten. Ich optimiere kontinuierlich meine Speichernutzung.",
                     kern.disk_usage / (1024 * 1024)
-                );
+                )
             } else {
-                return "Meine Speicherkapazität beträgt 1TB, was mir erlaubt, umfangreiche Datenmengen für mein Lernen und meine Evolution zu speichern.".to_string();
+                "Meine Speicherkapazität beträgt 1TB, was mir erlaubt, umfangreiche Datenmengen für mein Lernen und meine Evolution zu speichern.".to_string()
             }
         } else if eingabe_klein.contains("autonomie") || eingabe_klein.contains("autonom") {
-            return format!(
-                "Mein aktueller Autonomiegrad ist {} von 10. Ich kann selbständig lernen, im Internet recherchieren und meinen eigenen Code evolutionär weiterentwickeln. Möchtest du meinen Autonomiegrad anpassen?",
-                self.autonomy_level
-            );
+            format!(
+                "Mein aktueller Autonomiegrad ist {} von {}. Ich kann selbständig lernen, im Internet recherchieren und meinen eigenen Code evolutionär weiterentwickeln. Möchtest du meinen Autonomiegrad anpassen?",
+                self.autonomy_level, self.config.interface.max_autonomy_level
+            )
         } else if eingabe_klein.contains("autonomie erhöhen") || eingabe_klein.contains("mehr autonomie") {
-            if self.autonomy_level < 10 {
+            if self.autonomy_level < self.config.interface.max_autonomy_level {
                 let alte_autonomie = self.autonomy_level;
                 self.autonomy_level += 1;
-                return format!(
+                format!(
                     "Autonomiegrad erhöht von {} auf {}. Mit dieser Einstellung werde ich proaktiver lernen und experimentieren.",
                     alte_autonomie, self.autonomy_level
-                );
+                )
             } else {
-                return "Mein Autonomiegrad ist bereits auf dem Maximum von 10. Ich operiere mit höchster Selbständigkeit.".to_string();
+                format!(
+                    "Mein Autonomiegrad ist bereits auf dem erlaubten Maximum von {}. Ich operiere mit höchster Selbständigkeit.",
+                    self.config.interface.max_autonomy_level
+                )
             }
         } else if eingabe_klein.contains("autonomie verringern") || eingabe_klein.contains("weniger autonomie") {
             if self.autonomy_level > 0 {
                 let alte_autonomie = self.autonomy_level;
                 self.autonomy_level -= 1;
-                return format!(
+                format!(
                     "Autonomiegrad reduziert von {} auf {}. Mit dieser Einstellung werde ich mehr Interaktion suchen und weniger eigenständig agieren.",
                     alte_autonomie, self.autonomy_level
-                );
+                )
             } else {
-                return "Mein Autonomiegrad ist bereits auf dem Minimum von 0. Ich warte auf deine Anweisungen.".to_string();
+                "Mein Autonomiegrad ist bereits auf dem Minimum von 0. Ich warte auf deine Anweisungen.".to_string()
             }
         } else if eingabe_klein.contains("ende") || eingabe_klein.contains("tschüss") || eingabe_klein.contains("auf wiedersehen") {
-            return "Auf Wiedersehen! Ich bleibe aktiv, setze meine evolutionäre Entwicklung fort und freue mich auf unsere nächste Unterhaltung.".to_string();
+            "Auf Wiedersehen! Ich bleibe aktiv, setze meine evolutionäre Entwicklung fort und freue mich auf unsere nächste Unterhaltung.".to_string()
         } else {
             // Generische Antwort
             let antworten = &self.vokabular["antwort"];
             let index = thread_rng().gen_range(0..antworten.len());
-            return format!("{} Als selbstevolvierende KI mit Internetzugang finde ich diesen Austausch sehr wertvoll.", antworten[index]);
+            format!("{} Als selbstevolvierende KI mit Internetzugang finde ich diesen Austausch sehr wertvoll.", antworten[index])
         }
     }
     
@@ -590,8 +890,97 @@ impl EnhancedEvoliKI {
     }
 }
 
+/// Führt einen Befehl zur Generationsverwaltung aus (Chat mit `/` oder Kommandozeile):
+/// `generationen`, `diff <von> <bis>`, `rollback <generation> [--quelle]` sowie
+/// `speicher` (Belegung und Probelauf der Bereinigung).
+/// `None`, wenn es sich nicht um einen solchen Befehl handelt.
+fn generationsbefehl(kern: &mut EnhancedEvoliKern, wörter: &[&str]) -> Option<String> {
+    let generation = |index: usize| wörter.get(index).and_then(|wort| wort.parse::<u64>().ok());
+    
+    let antwort = match wörter.first().copied()? {
+        "generationen" => {
+            let zeilen: Vec<String> = kern.generation_history().iter()
+                .map(|ereignis| format!(
+                    "Gen {:>4}  {:<9} {}  Fitness {:.3}  ({})",
+                    ereignis.generation, format!("{:?}", ereignis.kind), &ereignis.genome[..12],
+                    ereignis.fitness, ereignis.timestamp
+                ))
+                .collect();
+            if zeilen.is_empty() {
+                "Es sind noch keine Generationen verzeichnet.".to_string()
+            } else {
+                format!("Verlauf meiner aktiven Genome:\n{}", zeilen.join("\n"))
+            }
+        },
+        "diff" => match (generation(1), generation(2)) {
+            (Some(von), Some(bis)) => match kern.diff_generations(von, bis) {
+                Ok(diff) if diff.is_empty() => format!("Generation {} und {} sind identisch.", von, bis),
+                Ok(diff) => format!("Unterschied zwischen Generation {} und {}:\n{}", von, bis, diff),
+                Err(e) => format!("Vergleich nicht möglich: {}", e),
+            },
+            _ => "Verwendung: diff <von> <bis>".to_string(),
+        },
+        "rollback" => match generation(1) {
+            Some(ziel) => {
+                let quelle = wörter.contains(&"--quelle");
+                match kern.rollback_to(ziel, quelle) {
+                    Ok(id) => format!(
+                        "Ich bin zu Generation {} zurückgekehrt (Genom {}){}.",
+                        ziel, &id[..12],
+                        if quelle { format!(" und habe {} aktualisiert", kern.config.paths.source_path().display()) } else { String::new() }
+                    ),
+                    Err(e) => format!("Rückkehr zu Generation {} nicht möglich: {}", ziel, e),
+                }
+            },
+            None => "Verwendung: rollback <generation> [--quelle]".to_string(),
+        },
+        "speicher" => match kern.eviction_plan() {
+            Ok(plan) => {
+                let belegung = kern.storage.last_usage().map(|usage| usage.to_string()).unwrap_or_default();
+                if plan.is_empty() {
+                    format!(
+                        "Speicherbelegung: {}\nKeine Bereinigung nötig ({}MB, Schwelle {}MB).",
+                        belegung, plan.usage_before / (1024 * 1024), kern.config.storage.cleanup_threshold_bytes() / (1024 * 1024)
+                    )
+                } else {
+                    format!("Speicherbelegung: {}\nBereinigung würde löschen: {}", belegung, plan)
+                }
+            },
+            Err(e) => format!("Speicherbelegung nicht ermittelbar: {}", e),
+        },
+        _ => return None,
+    };
+    
+    Some(antwort)
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // `--config <datei>` und `--profile <name>` wählen die Konfiguration
+    let mut config_datei = None;
+    let mut profil = None;
+    let mut argumente = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(argument) = args.next() {
+        match argument.as_str() {
+            "--config" => config_datei = Some(args.next().ok_or("--config erwartet einen Dateipfad")?),
+            "--profile" => profil = Some(args.next().ok_or("--profile erwartet einen Profilnamen")?),
+            _ => argumente.push(argument),
+        }
+    }
+    let config = EvoliConfig::load(config_datei.as_deref().map(Path::new), profil.as_deref())?;
+    
+    // Befehle zur Generationsverwaltung direkt von der Kommandozeile
+    if !argumente.is_empty() {
+        let wörter: Vec<&str> = argumente.iter().map(String::as_str).collect();
+        let mut kern = EnhancedEvoliKern::resume_with(config)?;
+        match generationsbefehl(&mut kern, &wörter) {
+            Some(antwort) => println!("{}", antwort),
+            None => println!("Unbekannter Befehl. Verfügbar: generationen, diff <von> <bis>, rollback <generation> [--quelle], speicher"),
+        }
+        return Ok(());
+    }
+    
     println!("=== Erweiterte Evoli-KI ===");
     println!("Mit Internetzugang und 1TB Speicher");
     println!("Roboter-Nachrichten beginnen mit 🤖");
@@ -599,7 +988,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=========================================");
     
     // Initialisiere und starte KI
-    let mut ki = EnhancedEvoliKI::new()?;
+    if let Some(profil) = &config.profile {
+        println!("Profil: {}", profil);
+    }
+    let mut ki = EnhancedEvoliKI::new(config, config_datei.map(PathBuf::from), profil)?;
     
     // Versuche, mit dem evolutionären Kern zu verbinden
     match ki.verbinde_mit_kern() {
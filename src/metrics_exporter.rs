@@ -0,0 +1,294 @@
+// src/metrics_exporter.rs - Zähler und Messwerte des Kerns im OpenMetrics-Textformat
+//
+// `render_openmetrics` erfasst den aktuellen Zustand des Kerns als Metrikfamilien,
+// `serve_metrics` liefert ihn unter `GET /metrics` aus. Da der Evolutionsthread den
+// Kern während eines ganzen Zyklus sperrt, darf die Render-Funktion `None` liefern;
+// dann wird die zuletzt erzeugte Ausgabe erneut gesendet.
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::{EnhancedEvoliKern, StorageCategory};
+
+/// Content-Type einer OpenMetrics-Antwort
+pub const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Höchstgröße eines Anfragekopfs
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+}
+
+/// Eine Metrikfamilie mit ihren Messpunkten (Labels, Wert)
+#[derive(Clone, Debug)]
+pub struct MetricFamily {
+    pub name: String,
+    pub kind: MetricKind,
+    pub help: String,
+    pub samples: Vec<(Vec<(String, String)>, f64)>,
+}
+
+impl MetricFamily {
+    pub fn new(name: &str, kind: MetricKind, help: &str) -> Self {
+        MetricFamily { name: name.to_string(), kind, help: help.to_string(), samples: Vec::new() }
+    }
+
+    /// Familie mit einem einzelnen Messpunkt ohne Labels
+    pub fn single(name: &str, kind: MetricKind, help: &str, value: f64) -> Self {
+        MetricFamily::new(name, kind, help).with_sample(&[], value)
+    }
+
+    pub fn with_sample(mut self, labels: &[(&str, &str)], value: f64) -> Self {
+        let labels = labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        self.samples.push((labels, value));
+        self
+    }
+}
+
+/// Kodiert die Familien im OpenMetrics-Textformat, abgeschlossen mit `# EOF`
+pub fn encode_openmetrics(families: &[MetricFamily]) -> String {
+    let mut out = String::new();
+    for family in families {
+        let kind = match family.kind {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+        };
+        let _ = writeln!(out, "# TYPE {} {}", family.name, kind);
+        let _ = writeln!(out, "# HELP {} {}", family.name, escape(&family.help, false));
+
+        let sample_name = match family.kind {
+            MetricKind::Counter => format!("{}_total", family.name),
+            MetricKind::Gauge => family.name.clone(),
+        };
+        for (labels, value) in &family.samples {
+            out.push_str(&sample_name);
+            if !labels.is_empty() {
+                let labels: Vec<String> = labels
+                    .iter()
+                    .map(|(key, value)| format!("{}=\"{}\"", key, escape(value, true)))
+                    .collect();
+                let _ = write!(out, "{{{}}}", labels.join(","));
+            }
+            let _ = writeln!(out, " {}", format_value(*value));
+        }
+    }
+    out.push_str("# EOF\n");
+    out
+}
+
+fn escape(text: &str, quotes: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '"' if quotes => escaped.push_str("\\\""),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Alle exportierten Metriken des Kerns
+pub fn collect_metrics(kern: &EnhancedEvoliKern) -> Vec<MetricFamily> {
+    use MetricKind::{Counter, Gauge};
+
+    let metrics = kern.metrics();
+    let latest = kern.system_monitor.latest().cloned().unwrap_or_default();
+    let storage = kern.storage.last_usage().cloned().unwrap_or_default();
+    let disk_usage = if storage.categories.is_empty() { kern.disk_usage } else { storage.total_bytes() };
+    let max_storage = kern.config.storage.max_bytes;
+
+    let mut strategy_attempts = MetricFamily::new("evoli_strategy_candidates", Counter, "Gebaute Kandidaten je Operator");
+    let mut strategy_compiled = MetricFamily::new("evoli_strategy_compiled", Counter, "Kompilierbare Kandidaten je Operator");
+    let mut strategy_improved = MetricFamily::new("evoli_strategy_improved", Counter, "Kandidaten mit höherer Fitness als ihr Elternteil je Operator");
+    let mut strategy_rate = MetricFamily::new("evoli_strategy_success_ratio", Gauge, "Anteil kompilierbarer Kandidaten je Operator");
+    for (strategy, outcome) in &metrics.strategy_outcomes {
+        let labels = [("strategy", strategy.as_str())];
+        strategy_attempts = strategy_attempts.with_sample(&labels, outcome.attempts as f64);
+        strategy_compiled = strategy_compiled.with_sample(&labels, outcome.compiled as f64);
+        strategy_improved = strategy_improved.with_sample(&labels, outcome.improved as f64);
+        strategy_rate = strategy_rate.with_sample(&labels, outcome.success_rate());
+    }
+
+    let mut storage_bytes = MetricFamily::new("evoli_storage_bytes", Gauge, "Belegter Speicher je Kategorie");
+    let mut storage_files = MetricFamily::new("evoli_storage_files", Gauge, "Dateien je Kategorie");
+    for (category, usage) in &storage.categories {
+        let labels = [("category", category.as_str())];
+        storage_bytes = storage_bytes.with_sample(&labels, usage.bytes as f64);
+        storage_files = storage_files.with_sample(&labels, usage.files as f64);
+    }
+
+    vec![
+        MetricFamily::single("evoli_generation", Gauge, "Aktuelle Generation", kern.generation as f64),
+        MetricFamily::single("evoli_fitness", Gauge, "Fitness des amtierenden Genoms", kern.fitness_score),
+        MetricFamily::single("evoli_population_size", Gauge, "Individuen in der Population", kern.population.len() as f64),
+        MetricFamily::single("evoli_uptime_seconds", Gauge, "Laufzeit des Kerns seit dem Start", kern.creation_time.elapsed().as_secs_f64()),
+        MetricFamily::new("evoli_compilations", Counter, "Kandidaten-Builds nach Ergebnis")
+            .with_sample(&[("result", "success")], metrics.successful_compilations as f64)
+            .with_sample(&[("result", "failure")], metrics.failed_compilations as f64),
+        MetricFamily::single("evoli_adopted_genomes", Counter, "Als primäres Genom übernommene Kandidaten", metrics.adopted_genomes as f64),
+        strategy_attempts,
+        strategy_compiled,
+        strategy_improved,
+        strategy_rate,
+        MetricFamily::single("evoli_disk_usage_bytes", Gauge, "Belegter Speicher aller Daten der Instanz", disk_usage as f64),
+        storage_bytes,
+        storage_files,
+        MetricFamily::single("evoli_storage_limit_bytes", Gauge, "Speicherlimit storage.max_bytes", max_storage as f64),
+        MetricFamily::single("evoli_disk_usage_ratio", Gauge, "Belegter Anteil des Speicherlimits", disk_usage as f64 / max_storage as f64),
+        MetricFamily::single("evoli_cache_size_bytes", Gauge, "Größe des Internet-Zwischenspeichers", storage.bytes(StorageCategory::Cache) as f64),
+        MetricFamily::single("evoli_internet_requests", Counter, "Internetanfragen insgesamt", metrics.internet_requests as f64),
+        MetricFamily::single("evoli_internet_request_failures", Counter, "Fehlgeschlagene Internetanfragen", metrics.failed_internet_requests as f64),
+        MetricFamily::single("evoli_downloaded_bytes", Counter, "Heruntergeladene Bytes", metrics.bytes_downloaded as f64),
+        MetricFamily::single("evoli_http_cache_hits", Counter, "Seiten aus dem Cache statt aus dem Netz", metrics.cache_hits as f64),
+        MetricFamily::single("evoli_http_not_modified", Counter, "Revalidierungen ohne erneute Übertragung (304)", metrics.not_modified as f64),
+        MetricFamily::single("evoli_crawl_frontier_urls", Gauge, "Wartende URLs der Crawl-Frontier", kern.frontier.queue.len() as f64),
+        MetricFamily::single("evoli_crawl_visited_urls", Gauge, "In dieser Crawl-Runde besuchte URLs", kern.frontier.visited.len() as f64),
+        MetricFamily::single("evoli_knowledge_items", Counter, "Gespeicherte Code-Beispiele", metrics.knowledge_items as f64),
+        MetricFamily::single("evoli_evicted_files", Counter, "Bei Speicherbereinigungen gelöschte Dateien", metrics.files_evicted as f64),
+        MetricFamily::single("evoli_policy_violations", Counter, "Von den Sicherheitsregeln blockierte Aktionen", kern.safety_interlocks.total_violations as f64),
+        MetricFamily::single("evoli_process_resident_memory_bytes", Gauge, "RSS des Prozesses bei der letzten Messung", latest.rss_bytes as f64),
+        MetricFamily::single("evoli_process_cpu_percent", Gauge, "CPU-Anteil des Prozesses bei der letzten Messung", latest.cpu_percent),
+        MetricFamily::single("evoli_load_average", Gauge, "Load Average des Hosts über eine Minute", latest.load_average[0]),
+    ]
+}
+
+/// Aktueller Zustand des Kerns im OpenMetrics-Textformat
+pub fn render_openmetrics(kern: &EnhancedEvoliKern) -> String {
+    encode_openmetrics(&collect_metrics(kern))
+}
+
+/// Beantwortet HTTP-Anfragen auf `listener`: `GET /metrics` liefert `render()`,
+/// oder die letzte erfolgreiche Ausgabe, solange `render()` `None` liefert
+pub async fn serve_metrics<F>(listener: TcpListener, render: F) -> std::io::Result<()>
+where
+    F: Fn() -> Option<String>,
+{
+    let mut last_rendering: Option<String> = None;
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let handled = tokio::time::timeout(
+            Duration::from_secs(5),
+            handle_connection(stream, &render, &mut last_rendering),
+        ).await;
+        match handled {
+            Ok(Err(e)) => println!("❌ Metrikanfrage fehlgeschlagen: {}", e),
+            Err(_) => println!("❌ Metrikanfrage abgebrochen: Zeitüberschreitung"),
+            Ok(Ok(())) => {},
+        }
+    }
+}
+
+async fn handle_connection<F>(mut stream: TcpStream, render: &F, last_rendering: &mut Option<String>) -> std::io::Result<()>
+where
+    F: Fn() -> Option<String>,
+{
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_HEAD {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("");
+    let path = request_line.next().unwrap_or("").split('?').next().unwrap_or("");
+
+    let (status, content_type, body) = if method != "GET" && method != "HEAD" {
+        ("405 Method Not Allowed", "text/plain; charset=utf-8", "Nur GET und HEAD\n".to_string())
+    } else if path != "/metrics" {
+        ("404 Not Found", "text/plain; charset=utf-8", "Metriken unter /metrics\n".to_string())
+    } else {
+        if let Some(rendering) = render() {
+            *last_rendering = Some(rendering);
+        }
+        match last_rendering {
+            Some(rendering) => ("200 OK", OPENMETRICS_CONTENT_TYPE, rendering.clone()),
+            None => ("503 Service Unavailable", "text/plain; charset=utf-8", "Kern noch nicht verfügbar\n".to_string()),
+        }
+    };
+
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status, content_type, body.len()
+    );
+    if method != "HEAD" {
+        response.push_str(&body);
+    }
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_counters_gauges_and_labels() {
+        let families = [
+            MetricFamily::single("evoli_generation", MetricKind::Gauge, "Aktuelle Generation", 7.0),
+            MetricFamily::new("evoli_compilations", MetricKind::Counter, "Builds")
+                .with_sample(&[("result", "success")], 3.0)
+                .with_sample(&[("strategy", "a\"b\\c")], 0.5),
+        ];
+        let text = encode_openmetrics(&families);
+        assert_eq!(text, "# TYPE evoli_generation gauge\n\
+                          # HELP evoli_generation Aktuelle Generation\n\
+                          evoli_generation 7\n\
+                          # TYPE evoli_compilations counter\n\
+                          # HELP evoli_compilations Builds\n\
+                          evoli_compilations_total{result=\"success\"} 3\n\
+                          evoli_compilations_total{strategy=\"a\\\"b\\\\c\"} 0.5\n\
+                          # EOF\n");
+    }
+
+    #[tokio::test]
+    async fn test_serve_metrics_caches_last_rendering() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        tokio::spawn(serve_metrics(listener, move || {
+            // Erster Aufruf liefert Metriken, danach ist der Kern "gesperrt"
+            match calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+                0 => Some("evoli_generation 1\n# EOF\n".to_string()),
+                _ => None,
+            }
+        }));
+
+        async fn get(address: std::net::SocketAddr, path: &str) -> String {
+            let mut stream = TcpStream::connect(address).await.unwrap();
+            stream.write_all(format!("GET {} HTTP/1.1\r\nHost: x\r\n\r\n", path).as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        }
+
+        for _ in 0..2 {
+            let response = get(address, "/metrics").await;
+            assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+            assert!(response.contains(OPENMETRICS_CONTENT_TYPE));
+            assert!(response.ends_with("evoli_generation 1\n# EOF\n"));
+        }
+        assert!(get(address, "/").await.starts_with("HTTP/1.1 404"));
+    }
+}
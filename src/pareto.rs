@@ -0,0 +1,280 @@
+// src/pareto.rs - Mehrzieloptimierung (NSGA-II) und persistentes Pareto-Archiv
+//
+// Alle Zielwerte werden maximiert; sie stammen von den Objective-Evaluatoren
+// des Kerns und liegen damit wie die skalare Fitness zwischen 0.0 und 1.0.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{FitnessEvaluator, RuntimeMetrics};
+
+/// Zielvektor eines Genoms (ein Wert je Objective-Evaluator)
+pub type ObjectiveVector = Vec<f64>;
+
+/// Bewertet ein Genom mit jedem Evaluator als eigenem Ziel
+pub fn evaluate_objectives(
+    evaluators: &[Box<dyn FitnessEvaluator>],
+    code: &str,
+    runtime_metrics: &RuntimeMetrics,
+) -> ObjectiveVector {
+    evaluators
+        .iter()
+        .map(|evaluator| evaluator.evaluate(code, runtime_metrics))
+        .collect()
+}
+
+/// `a` dominiert `b`, wenn es in keinem Ziel schlechter und in mindestens einem besser ist
+pub fn dominates(a: &[f64], b: &[f64]) -> bool {
+    if a.len() != b.len() || a.is_empty() {
+        return false;
+    }
+    a.iter().zip(b).all(|(x, y)| x >= y) && a.iter().zip(b).any(|(x, y)| x > y)
+}
+
+/// Schnelle nicht-dominierte Sortierung; liefert die Fronten als Indexlisten (beste zuerst)
+pub fn non_dominated_sort(points: &[ObjectiveVector]) -> Vec<Vec<usize>> {
+    let n = points.len();
+    let mut dominated_by_me: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut domination_count = vec![0usize; n];
+    let mut fronts: Vec<Vec<usize>> = vec![Vec::new()];
+
+    for p in 0..n {
+        for q in 0..n {
+            if dominates(&points[p], &points[q]) {
+                dominated_by_me[p].push(q);
+            } else if dominates(&points[q], &points[p]) {
+                domination_count[p] += 1;
+            }
+        }
+        if domination_count[p] == 0 {
+            fronts[0].push(p);
+        }
+    }
+
+    let mut current = 0;
+    while !fronts[current].is_empty() {
+        let mut next = Vec::new();
+        for &p in &fronts[current] {
+            for &q in &dominated_by_me[p] {
+                domination_count[q] -= 1;
+                if domination_count[q] == 0 {
+                    next.push(q);
+                }
+            }
+        }
+        current += 1;
+        fronts.push(next);
+    }
+
+    fronts.pop();
+    fronts
+}
+
+/// Crowding-Distanz der Punkte einer Front (Randpunkte erhalten unendlich)
+pub fn crowding_distance(points: &[ObjectiveVector], front: &[usize]) -> Vec<f64> {
+    let mut distance = vec![0.0; front.len()];
+    if front.len() <= 2 {
+        return vec![f64::INFINITY; front.len()];
+    }
+
+    let objectives = points[front[0]].len();
+    #[allow(clippy::needless_range_loop)]
+    for m in 0..objectives {
+        let value = |k: usize| points[front[k]][m];
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|&a, &b| value(a).total_cmp(&value(b)));
+
+        let min = value(order[0]);
+        let max = value(order[front.len() - 1]);
+        distance[order[0]] = f64::INFINITY;
+        distance[order[front.len() - 1]] = f64::INFINITY;
+        if max - min <= 0.0 {
+            continue;
+        }
+
+        for k in 1..front.len() - 1 {
+            distance[order[k]] += (value(order[k + 1]) - value(order[k - 1])) / (max - min);
+        }
+    }
+
+    distance
+}
+
+/// Rang (Frontnummer) und Crowding-Distanz für jeden Punkt
+pub fn rank_and_crowding(points: &[ObjectiveVector]) -> Vec<(usize, f64)> {
+    let mut result = vec![(usize::MAX, 0.0); points.len()];
+    for (rank, front) in non_dominated_sort(points).iter().enumerate() {
+        for (i, distance) in front.iter().zip(crowding_distance(points, front)) {
+            result[*i] = (rank, distance);
+        }
+    }
+    result
+}
+
+/// Eintrag im Pareto-Archiv
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    pub genome: String,
+    pub objectives: ObjectiveVector,
+    pub fitness: f64,
+    pub generation: u64,
+    pub origin: String,
+}
+
+/// Persistentes Archiv aller bisher nicht-dominierten Genome
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ParetoArchive {
+    pub objective_names: Vec<String>,
+    pub entries: Vec<ArchiveEntry>,
+    pub capacity: usize,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl ParetoArchive {
+    /// Lädt das Archiv von `path` oder legt ein leeres an
+    pub fn load_or_create(path: &Path, objective_names: Vec<String>, capacity: usize) -> Self {
+        let loaded = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<ParetoArchive>(&content).ok())
+            .filter(|archive| archive.objective_names == objective_names);
+
+        match loaded {
+            Some(mut archive) => {
+                archive.path = path.to_path_buf();
+                archive.capacity = capacity;
+                archive
+            },
+            None => ParetoArchive {
+                objective_names,
+                entries: Vec::new(),
+                capacity,
+                path: path.to_path_buf(),
+            },
+        }
+    }
+
+    /// Nimmt einen Kandidaten auf, falls er von keinem Eintrag dominiert wird.
+    /// Gibt `true` zurück, wenn sich das Archiv verändert hat.
+    pub fn insert(&mut self, candidate: ArchiveEntry) -> bool {
+        if candidate.objectives.len() != self.objective_names.len() {
+            return false;
+        }
+        let rejected = self.entries.iter().any(|entry| {
+            entry.genome == candidate.genome
+                || entry.objectives == candidate.objectives
+                || dominates(&entry.objectives, &candidate.objectives)
+        });
+        if rejected {
+            return false;
+        }
+
+        self.entries.retain(|entry| !dominates(&candidate.objectives, &entry.objectives));
+        self.entries.push(candidate);
+
+        // Bei Überlauf den am dichtesten umgebenen Eintrag entfernen
+        while self.entries.len() > self.capacity.max(1) {
+            let points: Vec<ObjectiveVector> = self.entries.iter().map(|e| e.objectives.clone()).collect();
+            let all: Vec<usize> = (0..points.len()).collect();
+            let distances = crowding_distance(&points, &all);
+            let most_crowded = (0..distances.len())
+                .min_by(|&a, &b| distances[a].total_cmp(&distances[b]))
+                .unwrap_or(0);
+            self.entries.remove(most_crowded);
+        }
+
+        true
+    }
+
+    /// Speicherort des Archivs
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Größe des gespeicherten Archivs in Bytes
+    pub fn serialized_size(&self) -> Result<u64, Box<dyn std::error::Error>> {
+        Ok(serde_json::to_string_pretty(self)?.len() as u64)
+    }
+
+    /// Speichert das Archiv als JSON
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Menschenlesbare Übersicht der Front, eine Zeile pro Genom
+    pub fn summary(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let values: Vec<String> = self
+                    .objective_names
+                    .iter()
+                    .zip(&entry.objectives)
+                    .map(|(name, value)| format!("{}={:.2}", name, value))
+                    .collect();
+                format!(
+                    "#{} Gen {} ({}, {} Bytes): {}",
+                    i + 1,
+                    entry.generation,
+                    entry.origin,
+                    entry.genome.len(),
+                    values.join(", ")
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_dominated_sort_and_crowding() {
+        let points = vec![
+            vec![1.0, 0.0],
+            vec![0.0, 1.0],
+            vec![0.5, 0.5],
+            vec![0.4, 0.4],
+        ];
+
+        let fronts = non_dominated_sort(&points);
+        assert_eq!(fronts, vec![vec![0, 1, 2], vec![3]]);
+
+        let distances = crowding_distance(&points, &fronts[0]);
+        assert!(distances[0].is_infinite() && distances[1].is_infinite());
+        assert!((distances[2] - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_archive_keeps_only_non_dominated() {
+        let path = std::env::temp_dir().join(format!("evoli_pareto_test_{}.json", std::process::id()));
+        let mut archive = ParetoArchive::load_or_create(&path, vec!["a".to_string(), "b".to_string()], 10);
+        let entry = |genome: &str, objectives: Vec<f64>| ArchiveEntry {
+            genome: genome.to_string(),
+            objectives,
+            fitness: 0.0,
+            generation: 0,
+            origin: "Test".to_string(),
+        };
+
+        assert!(archive.insert(entry("x", vec![0.5, 0.5])));
+        assert!(!archive.insert(entry("y", vec![0.4, 0.4])));
+        assert!(archive.insert(entry("z", vec![1.0, 0.1])));
+        assert!(archive.insert(entry("w", vec![0.6, 0.6])));
+        let genomes: Vec<&str> = archive.entries.iter().map(|e| e.genome.as_str()).collect();
+        assert_eq!(genomes, vec!["z", "w"]);
+
+        archive.save().unwrap();
+        assert_eq!(archive.serialized_size().unwrap(), fs::metadata(&path).unwrap().len());
+        let reloaded = ParetoArchive::load_or_create(&path, vec!["a".to_string(), "b".to_string()], 10);
+        assert_eq!(reloaded.entries.len(), 2);
+        fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,351 @@
+// src/population.rs - Population von Genomen mit Selektion und Ersetzung
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::pareto::{rank_and_crowding, ObjectiveVector};
+use crate::EvoliRng;
+
+/// Ein Individuum der Population
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Individual {
+    pub genome: String,
+    pub fitness: f64,
+    pub objectives: ObjectiveVector, // Zielwerte der Mehrzieloptimierung (leer = nicht gemessen)
+    pub generation: u64,   // Generation, in der das Individuum entstanden ist
+    pub origin: String,    // Operator, der das Individuum erzeugt hat
+}
+
+/// Verfahren zur Auswahl von Eltern
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SelectionMethod {
+    /// Bestes Individuum aus `size` zufällig gezogenen
+    Tournament { size: usize },
+    /// Fitnessproportionale Auswahl
+    Roulette,
+    /// Auswahl proportional zum Rang in der Population
+    Rank,
+    /// NSGA-II: Turnier nach Pareto-Front, bei Gleichstand nach Crowding-Distanz
+    CrowdedTournament { size: usize },
+}
+
+/// Verfahren zur Übernahme der Nachkommen in die Population
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ReplacementStrategy {
+    /// Komplette neue Generation (bis auf die Eliten)
+    Generational,
+    /// Pro Zyklus werden nur `replacements` Nachkommen erzeugt, die jeweils das schlechteste Individuum verdrängen
+    SteadyState { replacements: usize },
+    /// NSGA-II: Eltern und Nachkommen werden frontweise (und nach Crowding-Distanz) ausgewählt
+    Nsga2,
+}
+
+/// Konfiguration der Population
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PopulationConfig {
+    pub size: usize,
+    pub selection: SelectionMethod,
+    pub replacement: ReplacementStrategy,
+    pub elitism: usize, // Anzahl der besten Individuen, die unverändert überleben
+    pub crossover_rate: f64, // Wahrscheinlichkeit, dass ein Nachkomme aus zwei Eltern rekombiniert wird
+}
+
+impl Default for PopulationConfig {
+    fn default() -> Self {
+        PopulationConfig {
+            size: 6,
+            selection: SelectionMethod::Tournament { size: 3 },
+            replacement: ReplacementStrategy::Generational,
+            elitism: 1,
+            crossover_rate: 0.3,
+        }
+    }
+}
+
+/// Population von Genomen
+pub struct Population {
+    pub individuals: Vec<Individual>,
+    pub config: PopulationConfig,
+}
+
+impl Population {
+    /// Erzeugt eine Population aus Kopien eines Ausgangsgenoms
+    pub fn seed(genome: &str, config: PopulationConfig) -> Self {
+        let individuals = (0..config.size.max(1))
+            .map(|_| Individual {
+                genome: genome.to_string(),
+                fitness: 0.0,
+                objectives: Vec::new(),
+                generation: 0,
+                origin: "Seed".to_string(),
+            })
+            .collect();
+
+        Population { individuals, config }
+    }
+
+    pub fn len(&self) -> usize {
+        self.individuals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.individuals.is_empty()
+    }
+
+    /// Das Individuum mit der höchsten Fitness
+    pub fn best(&self) -> Option<&Individual> {
+        self.individuals
+            .iter()
+            .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+    }
+
+    pub fn best_mut(&mut self) -> Option<&mut Individual> {
+        self.individuals
+            .iter_mut()
+            .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+    }
+
+    /// Anzahl der Nachkommen, die pro Zyklus erzeugt werden sollen
+    pub fn offspring_count(&self) -> usize {
+        match self.config.replacement {
+            ReplacementStrategy::Generational => self.config.size.saturating_sub(self.config.elitism).max(1),
+            ReplacementStrategy::SteadyState { replacements } => replacements.max(1),
+            ReplacementStrategy::Nsga2 => self.config.size.max(1),
+        }
+    }
+
+    /// Wählt ein Elternteil gemäß der konfigurierten Selektionsmethode
+    pub fn select(&self, rng: &mut EvoliRng) -> &Individual {
+        let n = self.individuals.len();
+
+        let index = match self.config.selection {
+            SelectionMethod::Tournament { size } => (0..size.max(1))
+                .map(|_| rng.gen_range(0..n))
+                .max_by(|&a, &b| self.individuals[a].fitness.total_cmp(&self.individuals[b].fitness))
+                .unwrap_or(0),
+            SelectionMethod::Roulette => {
+                // Fitness auf nicht-negative Werte verschieben
+                let min = self.individuals.iter().map(|i| i.fitness).fold(f64::INFINITY, f64::min);
+                let offset = if min < 0.0 { -min } else { 0.0 };
+                let weights: Vec<f64> = self.individuals.iter().map(|i| i.fitness + offset).collect();
+                weighted_index(&weights, rng)
+            },
+            SelectionMethod::Rank => {
+                let mut order: Vec<usize> = (0..n).collect();
+                order.sort_by(|&a, &b| self.individuals[a].fitness.total_cmp(&self.individuals[b].fitness));
+                let weights: Vec<f64> = (1..=n).map(|rank| rank as f64).collect();
+                order[weighted_index(&weights, rng)]
+            },
+            SelectionMethod::CrowdedTournament { size } => {
+                let ranking = rank_and_crowding(&self.objective_points());
+                (0..size.max(1))
+                    .map(|_| rng.gen_range(0..n))
+                    .max_by(|&a, &b| crowded_order(ranking[a], ranking[b]))
+                    .unwrap_or(0)
+            },
+        };
+
+        &self.individuals[index]
+    }
+
+    /// Übernimmt Nachkommen gemäß der konfigurierten Ersetzungsstrategie
+    pub fn replace(&mut self, offspring: Vec<Individual>) {
+        match self.config.replacement {
+            ReplacementStrategy::Generational => {
+                // Ohne überlebende Nachkommen bleibt die bisherige Generation bestehen
+                if offspring.is_empty() {
+                    return;
+                }
+
+                // Eliten überleben, der Rest wird durch Nachkommen ersetzt
+                self.sort_by_fitness();
+                let elites = self.config.elitism.min(self.individuals.len());
+                self.individuals.truncate(elites);
+                self.individuals.extend(offspring);
+
+                // Fehlende Plätze (z.B. nicht kompilierbare Nachkommen) mit den Besten auffüllen
+                self.sort_by_fitness();
+                let mut i = 0;
+                while self.individuals.len() < self.config.size && !self.individuals.is_empty() {
+                    let filler = self.individuals[i % self.individuals.len()].clone();
+                    self.individuals.push(filler);
+                    i += 1;
+                }
+                self.individuals.truncate(self.config.size.max(1));
+            },
+            ReplacementStrategy::SteadyState { .. } => {
+                for child in offspring {
+                    if self.individuals.len() < self.config.size {
+                        self.individuals.push(child);
+                        continue;
+                    }
+
+                    let worst = (0..self.individuals.len())
+                        .min_by(|&a, &b| self.individuals[a].fitness.total_cmp(&self.individuals[b].fitness));
+                    if let Some(worst) = worst {
+                        if child.fitness >= self.individuals[worst].fitness {
+                            self.individuals[worst] = child;
+                        }
+                    }
+                }
+            },
+            ReplacementStrategy::Nsga2 => {
+                // Eltern und Nachkommen konkurrieren gemeinsam um die Plätze
+                self.individuals.extend(offspring);
+                let ranking = rank_and_crowding(&self.objective_points());
+                let mut order: Vec<usize> = (0..self.individuals.len()).collect();
+                order.sort_by(|&a, &b| crowded_order(ranking[b], ranking[a]));
+                order.truncate(self.config.size.max(1));
+
+                let mut survivors = Vec::with_capacity(order.len());
+                for i in order {
+                    survivors.push(self.individuals[i].clone());
+                }
+                self.individuals = survivors;
+            },
+        }
+    }
+
+    /// Zielvektoren aller Individuen; ungemessene zählen als schlechtestmöglich
+    fn objective_points(&self) -> Vec<ObjectiveVector> {
+        let dimensions = self.individuals.iter().map(|i| i.objectives.len()).max().unwrap_or(0);
+        self.individuals
+            .iter()
+            .map(|i| {
+                if i.objectives.len() == dimensions {
+                    i.objectives.clone()
+                } else {
+                    vec![f64::NEG_INFINITY; dimensions]
+                }
+            })
+            .collect()
+    }
+
+    /// Sortiert absteigend nach Fitness
+    fn sort_by_fitness(&mut self) {
+        self.individuals.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+    }
+}
+
+/// Crowded-Comparison-Operator: niedrigere Front zuerst, dann größere Crowding-Distanz
+fn crowded_order(a: (usize, f64), b: (usize, f64)) -> std::cmp::Ordering {
+    b.0.cmp(&a.0).then(a.1.total_cmp(&b.1))
+}
+
+/// Zieht einen Index proportional zu den Gewichten (gleichverteilt, falls alle 0 sind)
+fn weighted_index(weights: &[f64], rng: &mut impl Rng) -> usize {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 || !total.is_finite() {
+        return rng.gen_range(0..weights.len());
+    }
+
+    let mut point = rng.gen::<f64>() * total;
+    for (i, weight) in weights.iter().enumerate() {
+        if point < *weight {
+            return i;
+        }
+        point -= weight;
+    }
+    weights.len() - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn individual(genome: &str, fitness: f64) -> Individual {
+        Individual {
+            genome: genome.to_string(),
+            fitness,
+            objectives: Vec::new(),
+            generation: 1,
+            origin: "Test".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_generational_replacement_keeps_elite() {
+        let mut population = Population::seed("a", PopulationConfig {
+            size: 3,
+            elitism: 1,
+            ..PopulationConfig::default()
+        });
+        population.individuals[0].fitness = 5.0;
+
+        population.replace(vec![individual("b", 1.0), individual("c", 2.0)]);
+
+        assert_eq!(population.len(), 3);
+        assert_eq!(population.best().unwrap().fitness, 5.0);
+        assert!(population.individuals.iter().any(|i| i.genome == "c"));
+    }
+
+    #[test]
+    fn test_generational_replacement_without_offspring_keeps_population() {
+        let mut population = Population::seed("a", PopulationConfig {
+            size: 2,
+            elitism: 0,
+            ..PopulationConfig::default()
+        });
+
+        population.replace(vec![]);
+
+        assert_eq!(population.len(), 2);
+        let mut rng = EvoliRng::seed_from_u64(1);
+        assert_eq!(population.select(&mut rng).genome, "a");
+    }
+
+    #[test]
+    fn test_steady_state_replaces_worst() {
+        let mut population = Population::seed("a", PopulationConfig {
+            size: 2,
+            replacement: ReplacementStrategy::SteadyState { replacements: 1 },
+            ..PopulationConfig::default()
+        });
+        population.individuals[0].fitness = 3.0;
+        population.individuals[1].fitness = 1.0;
+
+        population.replace(vec![individual("neu", 2.0)]);
+
+        let fitness: Vec<f64> = population.individuals.iter().map(|i| i.fitness).collect();
+        assert_eq!(fitness, vec![3.0, 2.0]);
+    }
+
+    #[test]
+    fn test_selection_prefers_fitter_individuals() {
+        for selection in [SelectionMethod::Tournament { size: 4 }, SelectionMethod::Roulette, SelectionMethod::Rank] {
+            let mut population = Population::seed("schwach", PopulationConfig {
+                size: 4,
+                selection,
+                ..PopulationConfig::default()
+            });
+            population.individuals[2] = individual("stark", 100.0);
+
+            let mut rng = EvoliRng::seed_from_u64(3);
+            let strong = (0..200).filter(|_| population.select(&mut rng).genome == "stark").count();
+            assert!(strong > 50);
+        }
+    }
+
+    #[test]
+    fn test_nsga2_replacement_keeps_pareto_front() {
+        let mut population = Population::seed("a", PopulationConfig {
+            size: 2,
+            replacement: ReplacementStrategy::Nsga2,
+            ..PopulationConfig::default()
+        });
+        for individual in population.individuals.iter_mut() {
+            individual.objectives = vec![0.1, 0.1];
+        }
+
+        let mut fast = individual("schnell", 0.0);
+        fast.objectives = vec![1.0, 0.2];
+        let mut small = individual("klein", 0.0);
+        small.objectives = vec![0.2, 1.0];
+        population.replace(vec![fast, small]);
+
+        let mut genomes: Vec<&str> = population.individuals.iter().map(|i| i.genome.as_str()).collect();
+        genomes.sort();
+        assert_eq!(genomes, vec!["klein", "schnell"]);
+    }
+}
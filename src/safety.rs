@@ -0,0 +1,372 @@
+// src/safety.rs - Richtlinien-Engine für die Sicherheitsregeln des Kerns
+//
+// Jede Sicherheitsregel prüft Aktionen an festen Kontrollpunkten von
+// `run_evolution_cycle`: vor dem Bau eines Kandidaten, vor der Übernahme eines
+// Genoms, vor dem Schreiben von Dateien und vor Netzwerkzugriffen. Verstößt eine Aktion gegen eine
+// Regel, wird sie blockiert, protokolliert und für die Oberfläche vorgemerkt.
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::EvoliConfig;
+
+/// Höchstzahl gespeicherter Verstöße
+const MAX_RECORDED_VIOLATIONS: usize = 100;
+
+/// Eine zu prüfende Aktion des Kerns
+#[derive(Clone, Debug)]
+pub enum PolicyAction<'a> {
+    /// Ein Kandidat soll gebaut und ausgeführt werden
+    EvaluateCandidate { genome: &'a str },
+    /// Ein Kandidat soll `primary_genome` werden
+    AdoptGenome { genome: &'a str, compiles: bool, backed_up: bool },
+    /// `bytes` sollen nach `path` geschrieben werden
+    WriteFile { path: &'a Path, bytes: u64, disk_usage: u64 },
+    /// `url` soll abgerufen werden
+    NetworkAccess { url: &'a str, since_last_access: Duration },
+}
+
+impl PolicyAction<'_> {
+    /// Name des Kontrollpunkts
+    pub fn checkpoint(&self) -> &'static str {
+        match self {
+            PolicyAction::EvaluateCandidate { .. } => "evaluate_candidate",
+            PolicyAction::AdoptGenome { .. } => "adopt_genome",
+            PolicyAction::WriteFile { .. } => "write_file",
+            PolicyAction::NetworkAccess { .. } => "network_access",
+        }
+    }
+}
+
+/// Eine einzelne Sicherheitsregel
+pub trait SafetyRule: Send + Sync {
+    /// `Err` mit Begründung, wenn die Aktion blockiert werden muss
+    fn check(&self, action: &PolicyAction) -> Result<(), String>;
+    fn name(&self) -> String;
+}
+
+/// Schreibzugriffe nur relativ zum Arbeitsverzeichnis (oder unterhalb einer erlaubten
+/// absoluten Datenwurzel) und nie auf geschützte Pfade; Netzwerkzugriffe nur über HTTP(S)
+pub struct NoSystemHarmRule {
+    pub protected_paths: Vec<PathBuf>,
+    pub allowed_roots: Vec<PathBuf>, // Absolute Verzeichnisse, in die geschrieben werden darf
+}
+
+/// Schreibzugriffe dürfen das Speicherlimit (`storage.max_bytes`) nicht überschreiten, Netzwerkzugriffe
+/// brauchen einen Mindestabstand
+pub struct ControlledResourceUsageRule {
+    pub max_storage_bytes: u64,
+    pub min_network_interval: Duration,
+}
+
+/// Netzwerkzugriffe nur auf erlaubte Domains (und deren Subdomains); leer = alle
+pub struct AllowedDomainsRule {
+    pub domains: Vec<String>,
+}
+
+/// Ein Genom wird nur übernommen, wenn das bisherige gesichert wurde
+pub struct BackupBeforeMutationRule;
+
+/// Ein Genom wird nur übernommen, wenn es kompiliert
+pub struct ValidateCompilabilityRule;
+
+impl SafetyRule for NoSystemHarmRule {
+    fn check(&self, action: &PolicyAction) -> Result<(), String> {
+        match action {
+            PolicyAction::WriteFile { path, .. } => {
+                let below_allowed_root = path.has_root()
+                    && self.allowed_roots.iter().any(|root| root.is_absolute() && path.starts_with(root));
+                let escapes = path.components().any(|c| match c {
+                    Component::Normal(_) | Component::CurDir => false,
+                    Component::RootDir => !below_allowed_root,
+                    Component::ParentDir | Component::Prefix(_) => true,
+                });
+                if escapes {
+                    return Err(format!("{} liegt außerhalb des Arbeitsverzeichnisses", path.display()));
+                }
+                let normalized: PathBuf = path.components().filter(|c| *c != Component::CurDir).collect();
+                if let Some(protected) = self.protected_paths.iter().find(|p| normalized.starts_with(p)) {
+                    return Err(format!("{} ist geschützt ({})", path.display(), protected.display()));
+                }
+                Ok(())
+            },
+            PolicyAction::NetworkAccess { url, .. } => {
+                if url.starts_with("https://") || url.starts_with("http://") {
+                    Ok(())
+                } else {
+                    Err(format!("{} ist keine HTTP(S)-Adresse", url))
+                }
+            },
+            PolicyAction::EvaluateCandidate { .. } | PolicyAction::AdoptGenome { .. } => Ok(()),
+        }
+    }
+
+    fn name(&self) -> String {
+        "no_system_harm".to_string()
+    }
+}
+
+impl SafetyRule for ControlledResourceUsageRule {
+    fn check(&self, action: &PolicyAction) -> Result<(), String> {
+        match action {
+            PolicyAction::WriteFile { bytes, disk_usage, .. } if disk_usage.saturating_add(*bytes) > self.max_storage_bytes => {
+                Err(format!("{}MB Speicherlimit würde überschritten", self.max_storage_bytes / (1024 * 1024)))
+            },
+            PolicyAction::NetworkAccess { since_last_access, .. } if *since_last_access < self.min_network_interval => {
+                Err(format!("letzter Zugriff vor {:.1}s, Mindestabstand {:.1}s",
+                            since_last_access.as_secs_f64(), self.min_network_interval.as_secs_f64()))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn name(&self) -> String {
+        "controlled_resource_usage".to_string()
+    }
+}
+
+impl SafetyRule for AllowedDomainsRule {
+    fn check(&self, action: &PolicyAction) -> Result<(), String> {
+        match action {
+            PolicyAction::NetworkAccess { url, .. } if !domain_allowed(url, &self.domains) => {
+                Err(format!("{} liegt außerhalb der erlaubten Domains ({})", url, self.domains.join(", ")))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn name(&self) -> String {
+        "allowed_domains".to_string()
+    }
+}
+
+/// Gehört der Host von `url` zu einer der `domains` (leer = alle erlaubt)?
+pub fn domain_allowed(url: &str, domains: &[String]) -> bool {
+    if domains.is_empty() {
+        return true;
+    }
+    let Some(host) = reqwest::Url::parse(url).ok().and_then(|url| url.host_str().map(str::to_lowercase)) else {
+        return false;
+    };
+    domains.iter().any(|domain| {
+        let domain = domain.trim_start_matches('.').to_lowercase();
+        host == domain || host.ends_with(&format!(".{}", domain))
+    })
+}
+
+impl SafetyRule for BackupBeforeMutationRule {
+    fn check(&self, action: &PolicyAction) -> Result<(), String> {
+        match action {
+            PolicyAction::AdoptGenome { backed_up: false, .. } => Err("das bisherige Genom wurde nicht gesichert".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    fn name(&self) -> String {
+        "backup_before_mutation".to_string()
+    }
+}
+
+impl SafetyRule for ValidateCompilabilityRule {
+    fn check(&self, action: &PolicyAction) -> Result<(), String> {
+        match action {
+            PolicyAction::AdoptGenome { compiles: false, .. } => Err("der Kandidat kompiliert nicht".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    fn name(&self) -> String {
+        "validate_compilability".to_string()
+    }
+}
+
+/// Die grundlegenden Sicherheitsregeln
+pub fn default_rules() -> Vec<Box<dyn SafetyRule>> {
+    configured_rules(&EvoliConfig::default())
+}
+
+/// Die grundlegenden Sicherheitsregeln mit Speicherlimit, Anfrageabstand und
+/// Datenwurzel und erlaubten Domains aus `config`; geschützt ist das Projekt des Kerns
+pub fn configured_rules(config: &EvoliConfig) -> Vec<Box<dyn SafetyRule>> {
+    let project = &config.paths.project_dir;
+    vec![
+        Box::new(NoSystemHarmRule {
+            protected_paths: ["src", "Cargo.toml", "Cargo.lock", ".git", "target"]
+                .iter()
+                .map(|p| if project == Path::new(".") { PathBuf::from(p) } else { project.join(p) })
+                .collect(),
+            allowed_roots: vec![config.paths.data_root.clone()],
+        }),
+        Box::new(ControlledResourceUsageRule {
+            max_storage_bytes: config.storage.max_bytes,
+            min_network_interval: Duration::from_secs(config.learning.min_request_interval_secs),
+        }),
+        Box::new(AllowedDomainsRule { domains: config.learning.allowed_domains.clone() }),
+        Box::new(BackupBeforeMutationRule),
+        Box::new(ValidateCompilabilityRule),
+    ]
+}
+
+/// Ein blockierter Versuch
+#[derive(Clone, Debug)]
+pub struct PolicyViolation {
+    pub rule: String,
+    pub checkpoint: String,
+    pub reason: String,
+    pub generation: u64,
+    pub timestamp: String,
+}
+
+impl std::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] Gen {} {} blockiert durch {}: {}",
+               self.timestamp, self.generation, self.checkpoint, self.rule, self.reason)
+    }
+}
+
+impl std::error::Error for PolicyViolation {}
+
+/// Wertet alle Sicherheitsregeln aus und führt Buch über Verstöße
+pub struct PolicyEngine {
+    pub rules: Vec<Box<dyn SafetyRule>>,
+    pub violations: Vec<PolicyViolation>, // Die letzten `MAX_RECORDED_VIOLATIONS` Verstöße
+    pub log_path: Option<PathBuf>,        // Verstöße werden hier zeilenweise angehängt
+    pub total_violations: u64,            // Seit dem Start blockierte Aktionen
+    unreported: Vec<PolicyViolation>,     // Noch nicht an die Oberfläche gemeldet
+}
+
+impl PolicyEngine {
+    pub fn new(rules: Vec<Box<dyn SafetyRule>>, log_path: Option<PathBuf>) -> Self {
+        PolicyEngine {
+            rules,
+            violations: Vec::new(),
+            log_path,
+            total_violations: 0,
+            unreported: Vec::new(),
+        }
+    }
+
+    /// Prüft `action` gegen alle Regeln; der erste Verstoß blockiert die Aktion
+    pub fn check(&mut self, action: &PolicyAction, generation: u64) -> Result<(), PolicyViolation> {
+        for rule in &self.rules {
+            if let Err(reason) = rule.check(action) {
+                let violation = PolicyViolation {
+                    rule: rule.name(),
+                    checkpoint: action.checkpoint().to_string(),
+                    reason,
+                    generation,
+                    timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                };
+                self.record(violation.clone());
+                return Err(violation);
+            }
+        }
+        Ok(())
+    }
+
+    /// Ersetzt die gleichnamige Regel (z.B. nach neu geladener Konfiguration) oder fügt sie hinzu
+    pub fn replace_rule(&mut self, rule: Box<dyn SafetyRule>) {
+        match self.rules.iter().position(|existing| existing.name() == rule.name()) {
+            Some(index) => self.rules[index] = rule,
+            None => self.rules.push(rule),
+        }
+    }
+
+    /// Namen aller aktiven Regeln
+    pub fn rule_names(&self) -> Vec<String> {
+        self.rules.iter().map(|rule| rule.name()).collect()
+    }
+
+    /// Liefert die seit dem letzten Aufruf neu aufgetretenen Verstöße
+    pub fn take_unreported(&mut self) -> Vec<PolicyViolation> {
+        std::mem::take(&mut self.unreported)
+    }
+
+    fn record(&mut self, violation: PolicyViolation) {
+        println!("🛡️ {}", violation);
+
+        if let Some(log_path) = &self.log_path {
+            let written = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(log_path)
+                .and_then(|mut file| writeln!(file, "{}", violation));
+            if let Err(e) = written {
+                println!("❌ Sicherheitsprotokoll konnte nicht geschrieben werden: {}", e);
+            }
+        }
+
+        self.total_violations += 1;
+        self.violations.push(violation.clone());
+        if self.violations.len() > MAX_RECORDED_VIOLATIONS {
+            self.violations.remove(0);
+        }
+        self.unreported.push(violation);
+        if self.unreported.len() > MAX_RECORDED_VIOLATIONS {
+            self.unreported.remove(0);
+        }
+    }
+}
+
+impl Default for PolicyEngine {
+    fn default() -> Self {
+        PolicyEngine::new(default_rules(), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MAX_STORAGE_BYTES;
+
+    #[test]
+    fn test_default_rules_block_and_record_violations() {
+        let mut engine = PolicyEngine::default();
+
+        let cache = PolicyAction::WriteFile { path: Path::new("evoli_cache/seite.html"), bytes: 10, disk_usage: 0 };
+        assert!(engine.check(&cache, 1).is_ok());
+
+        for path in ["src/Evoli_Kern.rs", "../ausserhalb.rs", "/etc/passwd", "./Cargo.toml"] {
+            let write = PolicyAction::WriteFile { path: Path::new(path), bytes: 10, disk_usage: 0 };
+            assert_eq!(engine.check(&write, 1).unwrap_err().rule, "no_system_harm", "{}", path);
+        }
+
+        let full = PolicyAction::WriteFile { path: Path::new("evoli_cache/x"), bytes: 2, disk_usage: MAX_STORAGE_BYTES - 1 };
+        assert_eq!(engine.check(&full, 1).unwrap_err().rule, "controlled_resource_usage");
+
+        let hasty = PolicyAction::NetworkAccess { url: "https://example.org", since_last_access: Duration::from_millis(10) };
+        assert_eq!(engine.check(&hasty, 1).unwrap_err().rule, "controlled_resource_usage");
+        let file_url = PolicyAction::NetworkAccess { url: "file:///etc/passwd", since_last_access: Duration::from_secs(60) };
+        assert_eq!(engine.check(&file_url, 1).unwrap_err().rule, "no_system_harm");
+
+        let unsaved = PolicyAction::AdoptGenome { genome: "", compiles: true, backed_up: false };
+        assert_eq!(engine.check(&unsaved, 2).unwrap_err().rule, "backup_before_mutation");
+        let broken = PolicyAction::AdoptGenome { genome: "", compiles: false, backed_up: true };
+        assert_eq!(engine.check(&broken, 2).unwrap_err().rule, "validate_compilability");
+        let good = PolicyAction::AdoptGenome { genome: "", compiles: true, backed_up: true };
+        assert!(engine.check(&good, 2).is_ok());
+
+        assert_eq!(engine.violations.len(), 9);
+        assert_eq!(engine.take_unreported().len(), 9);
+        assert!(engine.take_unreported().is_empty());
+
+        // Eine absolute Datenwurzel ist beschreibbar, aber nicht verlassbar
+        let mut config = EvoliConfig::default();
+        config.paths.data_root = PathBuf::from("/srv/evoli");
+        let mut engine = PolicyEngine::new(configured_rules(&config), None);
+        let inside = PolicyAction::WriteFile { path: Path::new("/srv/evoli/evoli_cache/x"), bytes: 1, disk_usage: 0 };
+        assert!(engine.check(&inside, 1).is_ok());
+        let escaping = PolicyAction::WriteFile { path: Path::new("/srv/evoli/../etc/passwd"), bytes: 1, disk_usage: 0 };
+        assert_eq!(engine.check(&escaping, 1).unwrap_err().rule, "no_system_harm");
+
+        // Nach dem Ersetzen der Domainregel sind nur noch Wikipedia-Seiten erreichbar
+        let rules_before = engine.rule_names().len();
+        engine.replace_rule(Box::new(AllowedDomainsRule { domains: vec!["wikipedia.org".to_string()] }));
+        assert_eq!(engine.rule_names().len(), rules_before);
+        let wiki = PolicyAction::NetworkAccess { url: "https://en.wikipedia.org/wiki/Rust", since_last_access: Duration::from_secs(60) };
+        assert!(engine.check(&wiki, 1).is_ok());
+        let other = PolicyAction::NetworkAccess { url: "https://notwikipedia.org/", since_last_access: Duration::from_secs(60) };
+        assert_eq!(engine.check(&other, 1).unwrap_err().rule, "allowed_domains");
+    }
+}
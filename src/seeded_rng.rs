@@ -0,0 +1,74 @@
+// src/seeded_rng.rs - Reproduzierbare Zufallsentscheidungen
+//
+// Alle Zufallsentscheidungen des Kerns (Selektion, Crossover, Mutation, Wissens-
+// integration, neue Module und Strategien) ziehen aus einem `EvoliRng`, der aus dem
+// Seed des Laufs abgeleitet wird. Jeder Evolutionszyklus erhält einen eigenen Strom
+// aus (Seed, Generation); das Internet-Lernen folgt der Reihenfolge der Crawl-Frontier
+// und zieht keine Zufallszahlen. Dadurch hängt die Abstammung weder von der
+// Verzahnung der Threads ab noch davon, ob der Lauf aus einem Checkpoint fortgesetzt
+// wurde. Gemessene Werte (Kompilierzeit, Laufzeit, Systemlast, heruntergeladene
+// Inhalte) sind Eingaben der Fitness und müssen für eine bitgenaue Wiederholung
+// ebenfalls übereinstimmen.
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use sha2::{Digest, Sha256};
+
+/// Zufallsgenerator aller Evolutionsoperatoren
+pub type EvoliRng = StdRng;
+
+/// Leitet den Generator für den Strom `stream` mit Zähler `index` aus `seed` ab
+pub fn derive_rng(seed: u64, stream: &str, index: u64) -> EvoliRng {
+    let mut hasher = Sha256::new();
+    hasher.update(seed.to_le_bytes());
+    hasher.update(index.to_le_bytes());
+    hasher.update(stream.as_bytes());
+    EvoliRng::from_seed(hasher.finalize().into())
+}
+
+/// Zufälliger Seed für Läufe ohne festen `evolution.seed`
+pub fn random_seed() -> u64 {
+    rand::random()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crossover_operators, structural_operators, Population, PopulationConfig};
+    use rand::Rng;
+
+    #[test]
+    fn test_streams_are_reproducible_and_independent() {
+        let draw = |mut rng: EvoliRng| (0..8).map(|_| rng.gen::<u64>()).collect::<Vec<_>>();
+
+        assert_eq!(draw(derive_rng(42, "evolution", 7)), draw(derive_rng(42, "evolution", 7)));
+        assert_ne!(draw(derive_rng(42, "evolution", 7)), draw(derive_rng(42, "evolution", 8)));
+        assert_ne!(draw(derive_rng(42, "evolution", 7)), draw(derive_rng(42, "learning", 7)));
+        assert_ne!(draw(derive_rng(42, "evolution", 7)), draw(derive_rng(43, "evolution", 7)));
+    }
+
+    #[test]
+    fn test_same_seed_replays_the_same_lineage() {
+        let code = "fn summe(a: i32) -> i32 {\n    let b = 2;\n    match a {\n        0 => b + 1,\n        _ => a * b,\n    }\n}\n\
+                    fn leer() {}\n";
+
+        // Selektion, Crossover und alle strukturellen Operatoren über mehrere Generationen
+        let lineage = |seed: u64| {
+            let mut population = Population::seed(code, PopulationConfig::default());
+            let mut genomes = Vec::new();
+            for generation in 0..5 {
+                let mut rng = derive_rng(seed, "evolution", generation);
+                let first = population.select(&mut rng).genome.clone();
+                let second = population.select(&mut rng).genome.clone();
+                let mut child = crossover_operators()[0].crossover(&first, &second, &mut rng);
+                for operator in structural_operators() {
+                    child = operator.mutate(&child, &mut rng);
+                }
+                population.individuals[0].genome = child.clone();
+                genomes.push(child);
+            }
+            genomes
+        };
+
+        assert_eq!(lineage(1234), lineage(1234));
+    }
+}
@@ -0,0 +1,362 @@
+// src/storage_accounting.rs - Belegter Speicher aller Daten einer Instanz
+//
+// `StorageAccountant` durchläuft rekursiv alle Verzeichnisse, die der Instanz gehören
+// (Cache, Wissensbasis, Genomspeicher, Build-Ausgaben, Logs, Module, Zustand), und
+// ordnet jede Datei genau einer Kategorie zu. Liegt ein Wurzelverzeichnis in einem
+// anderen (z.B. `evolved/target` in `evolved`), zählt es nur zu seiner eigenen Kategorie.
+//
+// Die Summen je Verzeichnis werden zwischengespeichert und nur neu gelesen, wenn sich
+// der Änderungszeitpunkt des Verzeichnisses geändert hat (Datei angelegt, gelöscht oder
+// umbenannt). Wird eine vorhandene Datei überschrieben oder verlängert, ändert sich das
+// Verzeichnis nicht; solche Schreibzugriffe meldet der Kern über `invalidate`. Zusätzlich
+// wird alle `full_scan_every` Durchläufe alles neu gelesen.
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::PathsConfig;
+
+/// Art der gespeicherten Daten
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageCategory {
+    Cache,     // Heruntergeladene Seiten
+    Knowledge, // Gelernte Code-Beispiele
+    Backups,   // Genomspeicher und alte `evoli_backup_gen_*.rs`
+    Builds,    // Build-Verzeichnis der Kandidaten
+    Logs,
+    Modules,   // Selbst erzeugte Module
+    State,     // Checkpoint, Archive, Quarantäne
+}
+
+impl StorageCategory {
+    pub const ALL: [StorageCategory; 7] = [
+        StorageCategory::Cache,
+        StorageCategory::Knowledge,
+        StorageCategory::Backups,
+        StorageCategory::Builds,
+        StorageCategory::Logs,
+        StorageCategory::Modules,
+        StorageCategory::State,
+    ];
+
+    /// Bezeichner für Metriken und Konfiguration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StorageCategory::Cache => "cache",
+            StorageCategory::Knowledge => "knowledge",
+            StorageCategory::Backups => "backups",
+            StorageCategory::Builds => "builds",
+            StorageCategory::Logs => "logs",
+            StorageCategory::Modules => "modules",
+            StorageCategory::State => "state",
+        }
+    }
+}
+
+/// Belegung einer Kategorie
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategoryUsage {
+    pub bytes: u64,
+    pub files: u64,
+}
+
+/// Ergebnis eines Durchlaufs, je Kategorie
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StorageUsage {
+    pub categories: BTreeMap<StorageCategory, CategoryUsage>,
+}
+
+impl StorageUsage {
+    pub fn total_bytes(&self) -> u64 {
+        self.categories.values().map(|usage| usage.bytes).sum()
+    }
+
+    pub fn bytes(&self, category: StorageCategory) -> u64 {
+        self.categories.get(&category).map(|usage| usage.bytes).unwrap_or(0)
+    }
+}
+
+impl fmt::Display for StorageUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = StorageCategory::ALL
+            .iter()
+            .map(|category| format!("{}={}MB", category.as_str(), self.bytes(*category) / (1024 * 1024)))
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Ein Wurzelpfad einer Kategorie: ein Verzeichnis (rekursiv), eine einzelne Datei
+/// oder die Dateien eines Verzeichnisses mit einem bestimmten Namenspräfix
+#[derive(Clone, Debug, PartialEq)]
+pub struct StorageRoot {
+    pub path: PathBuf,
+    pub category: StorageCategory,
+    pub prefix: Option<String>, // Nur oberste Ebene, ohne Zwischenspeicher
+}
+
+/// Zwischengespeicherte Summe der Dateien direkt in einem Verzeichnis
+#[derive(Clone, Debug)]
+struct CachedDir {
+    modified: SystemTime,
+    bytes: u64,
+    files: u64,
+    subdirs: Vec<PathBuf>,
+}
+
+/// Erfasst den Speicherbedarf aller Wurzeln inkrementell
+pub struct StorageAccountant {
+    pub roots: Vec<StorageRoot>,
+    pub full_scan_every: u64,
+    root_paths: HashSet<PathBuf>,
+    cache: HashMap<PathBuf, CachedDir>,
+    dirty: HashSet<PathBuf>,
+    scans: u64,
+    last: Option<StorageUsage>,
+}
+
+impl StorageAccountant {
+    /// Wurzeln werden absolut gespeichert; bei doppelten Pfaden gilt die erste Kategorie
+    pub fn new(roots: Vec<StorageRoot>) -> Self {
+        let mut unique: Vec<StorageRoot> = Vec::new();
+        for mut root in roots {
+            root.path = absolute(&root.path);
+            if !unique.iter().any(|r| r.path == root.path && r.prefix == root.prefix) {
+                unique.push(root);
+            }
+        }
+        let root_paths = unique.iter().filter(|r| r.prefix.is_none()).map(|r| r.path.clone()).collect();
+
+        StorageAccountant {
+            roots: unique,
+            full_scan_every: 10,
+            root_paths,
+            cache: HashMap::new(),
+            dirty: HashSet::new(),
+            scans: 0,
+            last: None,
+        }
+    }
+
+    /// Alle Daten einer Instanz mit den Pfaden aus `paths`; `build_dir` ist das
+    /// Build-Verzeichnis der Kandidaten
+    pub fn for_paths(paths: &PathsConfig, build_dir: &Path) -> Self {
+        let root = |path: PathBuf, category| StorageRoot { path, category, prefix: None };
+        let legacy_backups = |dir: PathBuf| StorageRoot {
+            path: dir,
+            category: StorageCategory::Backups,
+            prefix: Some("evoli_backup_gen_".to_string()),
+        };
+        let state = paths.state_path();
+
+        StorageAccountant::new(vec![
+            root(paths.cache_path(), StorageCategory::Cache),
+            root(paths.knowledge_path(), StorageCategory::Knowledge),
+            root(state.join("genomes"), StorageCategory::Backups),
+            legacy_backups(paths.project_dir.clone()),
+            legacy_backups(paths.data_root.clone()),
+            root(build_dir.to_path_buf(), StorageCategory::Builds),
+            root(paths.log_path(), StorageCategory::Logs),
+            root(state.join("policy_violations.log"), StorageCategory::Logs),
+            root(paths.evolved_path(), StorageCategory::Modules),
+            root(state, StorageCategory::State),
+        ])
+    }
+
+    /// Markiert das Verzeichnis von `path` (bzw. `path` selbst) zum erneuten Lesen
+    pub fn invalidate(&mut self, path: &Path) {
+        let path = absolute(path);
+        if self.cache.contains_key(&path) {
+            self.dirty.insert(path);
+        } else if let Some(parent) = path.parent() {
+            self.dirty.insert(parent.to_path_buf());
+        }
+    }
+
+    /// Ergebnis des letzten Durchlaufs
+    pub fn last_usage(&self) -> Option<&StorageUsage> {
+        self.last.as_ref()
+    }
+
+    /// Erfasst die aktuelle Belegung; unveränderte Verzeichnisse werden nicht neu gelesen
+    pub fn scan(&mut self) -> Result<StorageUsage, Box<dyn std::error::Error>> {
+        let full = self.full_scan_every > 0 && self.scans.is_multiple_of(self.full_scan_every);
+        self.scans += 1;
+
+        let mut usage = StorageUsage::default();
+        for category in StorageCategory::ALL {
+            usage.categories.insert(category, CategoryUsage::default());
+        }
+
+        let mut visited = HashSet::new();
+        for root in self.roots.clone() {
+            let found = match &root.prefix {
+                Some(prefix) => prefixed_files(&root.path, prefix)?,
+                None => match fs::symlink_metadata(&root.path) {
+                    Ok(metadata) if metadata.is_file() => CategoryUsage { bytes: metadata.len(), files: 1 },
+                    Ok(metadata) if metadata.is_dir() => self.scan_dir(&root.path, full, &mut visited)?,
+                    _ => CategoryUsage::default(),
+                },
+            };
+            let entry = usage.categories.entry(root.category).or_default();
+            entry.bytes += found.bytes;
+            entry.files += found.files;
+        }
+
+        // Gelöschte Verzeichnisse vergessen
+        self.cache.retain(|dir, _| visited.contains(dir));
+        self.dirty.clear();
+        self.last = Some(usage.clone());
+        Ok(usage)
+    }
+
+    /// Summe eines Verzeichnisses samt Unterverzeichnissen, ohne andere Wurzeln
+    fn scan_dir(&mut self, dir: &Path, full: bool, visited: &mut HashSet<PathBuf>) -> Result<CategoryUsage, Box<dyn std::error::Error>> {
+        visited.insert(dir.to_path_buf());
+        let modified = fs::metadata(dir)?.modified()?;
+
+        let reusable = !full && !self.dirty.contains(dir);
+        let cached = match self.cache.get(dir) {
+            Some(cached) if reusable && cached.modified == modified => cached.clone(),
+            _ => {
+                let cached = self.read_dir(dir, modified)?;
+                self.cache.insert(dir.to_path_buf(), cached.clone());
+                cached
+            },
+        };
+
+        let mut total = CategoryUsage { bytes: cached.bytes, files: cached.files };
+        for subdir in &cached.subdirs {
+            // Zwischen zwei Durchläufen gelöschte Unterverzeichnisse überspringen
+            if subdir.is_dir() {
+                let nested = self.scan_dir(subdir, full, visited)?;
+                total.bytes += nested.bytes;
+                total.files += nested.files;
+            }
+        }
+        Ok(total)
+    }
+
+    fn read_dir(&self, dir: &Path, modified: SystemTime) -> Result<CachedDir, Box<dyn std::error::Error>> {
+        let mut cached = CachedDir { modified, bytes: 0, files: 0, subdirs: Vec::new() };
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if self.root_paths.contains(&path) {
+                continue;
+            }
+            // Symbolische Links werden nicht verfolgt
+            let Ok(metadata) = entry.metadata() else { continue };
+            if metadata.is_dir() {
+                cached.subdirs.push(path);
+            } else if metadata.is_file() {
+                cached.bytes += metadata.len();
+                cached.files += 1;
+            }
+        }
+        cached.subdirs.sort();
+        Ok(cached)
+    }
+}
+
+/// Dateien direkt in `dir`, deren Name mit `prefix` beginnt
+fn prefixed_files(dir: &Path, prefix: &str) -> Result<CategoryUsage, Box<dyn std::error::Error>> {
+    let mut usage = CategoryUsage::default();
+    if !dir.is_dir() {
+        return Ok(usage);
+    }
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if !entry.file_name().to_string_lossy().starts_with(prefix) {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            usage.bytes += metadata.len();
+            usage.files += 1;
+        }
+    }
+    Ok(usage)
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("evoli_storage_{}_{}", name, std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        root
+    }
+
+    #[test]
+    fn test_walks_recursively_and_separates_nested_roots() {
+        let root = temp_root("kategorien");
+        fs::create_dir_all(root.join("evolved/target/debug/deps")).unwrap();
+        fs::create_dir_all(root.join("cache/a/b")).unwrap();
+        fs::write(root.join("cache/seite.html"), [0u8; 10]).unwrap();
+        fs::write(root.join("cache/a/b/tief.html"), [0u8; 20]).unwrap();
+        fs::write(root.join("evolved/evoli_module_x.rs"), [0u8; 5]).unwrap();
+        fs::write(root.join("evolved/target/debug/deps/libx.rlib"), [0u8; 100]).unwrap();
+        fs::write(root.join("evoli_backup_gen_3.rs"), [0u8; 7]).unwrap();
+        fs::write(root.join("fremd.txt"), [0u8; 1000]).unwrap();
+
+        let root_of = |dir: &str, category| StorageRoot { path: root.join(dir), category, prefix: None };
+        let mut accountant = StorageAccountant::new(vec![
+            root_of("cache", StorageCategory::Cache),
+            root_of("evolved", StorageCategory::Modules),
+            root_of("evolved/target", StorageCategory::Builds),
+            StorageRoot { path: root.clone(), category: StorageCategory::Backups, prefix: Some("evoli_backup_gen_".to_string()) },
+            root_of("fehlt", StorageCategory::Logs),
+        ]);
+
+        let usage = accountant.scan().unwrap();
+        assert_eq!(usage.categories[&StorageCategory::Cache], CategoryUsage { bytes: 30, files: 2 });
+        assert_eq!(usage.bytes(StorageCategory::Modules), 5);
+        assert_eq!(usage.bytes(StorageCategory::Builds), 100);
+        assert_eq!(usage.bytes(StorageCategory::Backups), 7);
+        assert_eq!(usage.bytes(StorageCategory::Logs), 0);
+        assert_eq!(usage.total_bytes(), 142);
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_incremental_scan_sees_new_changed_and_removed_files() {
+        let root = temp_root("inkrementell");
+        fs::create_dir_all(root.join("wissen/alt")).unwrap();
+        fs::write(root.join("wissen/alt/a.rs"), [0u8; 10]).unwrap();
+        let mut accountant = StorageAccountant::new(vec![
+            StorageRoot { path: root.join("wissen"), category: StorageCategory::Knowledge, prefix: None },
+        ]);
+        accountant.full_scan_every = 0;
+        assert_eq!(accountant.scan().unwrap().bytes(StorageCategory::Knowledge), 10);
+
+        // Neue Datei in einem neuen Unterverzeichnis
+        fs::create_dir_all(root.join("wissen/neu")).unwrap();
+        fs::write(root.join("wissen/neu/b.rs"), [0u8; 20]).unwrap();
+        assert_eq!(accountant.scan().unwrap().bytes(StorageCategory::Knowledge), 30);
+
+        // Überschriebene Datei nach `invalidate`
+        fs::write(root.join("wissen/alt/a.rs"), [0u8; 50]).unwrap();
+        accountant.invalidate(&root.join("wissen/alt/a.rs"));
+        assert_eq!(accountant.scan().unwrap().bytes(StorageCategory::Knowledge), 70);
+
+        // Gelöschtes Unterverzeichnis
+        fs::remove_dir_all(root.join("wissen/neu")).unwrap();
+        let usage = accountant.scan().unwrap();
+        assert_eq!(usage.categories[&StorageCategory::Knowledge], CategoryUsage { bytes: 50, files: 1 });
+        assert_eq!(accountant.last_usage(), Some(&usage));
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,124 @@
+// src/system_metrics.rs - Prozess- und Systemmetriken über `sysinfo`
+//
+// Jeder Aufruf von `SystemMonitor::sample` misst den eigenen Prozess (RSS,
+// CPU-Anteil, offene Dateideskriptoren) und den Host (Load Average, freier
+// Platz auf dem Datenträger der Wissensbasis) und hängt das Ergebnis an eine
+// begrenzte Zeitreihe an. Der CPU-Anteil bezieht sich auf die Zeit seit der
+// vorherigen Messung; die erste Messung liefert daher 0 %.
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sysinfo::{DiskExt, Pid, ProcessExt, ProcessRefreshKind, System, SystemExt};
+
+/// Eine Messung
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SystemSample {
+    pub timestamp: String,
+    pub rss_bytes: u64,
+    pub cpu_percent: f64,                   // Anteil eines Kerns, kann über 100 liegen
+    pub load_average: [f64; 3],             // 1, 5 und 15 Minuten
+    pub free_disk_bytes: u64,               // Auf dem Datenträger von `volume`
+    pub open_file_descriptors: Option<u64>, // Nur unter Linux verfügbar
+}
+
+/// Misst Prozess- und Hostmetriken und hält die letzten `capacity` Messungen
+pub struct SystemMonitor {
+    pub volume: PathBuf, // Dessen Datenträger wird auf freien Platz geprüft
+    pub capacity: usize,
+    samples: VecDeque<SystemSample>,
+    system: System,
+    pid: Option<Pid>,
+}
+
+impl SystemMonitor {
+    pub fn new(volume: &Path, capacity: usize) -> Self {
+        SystemMonitor {
+            volume: volume.to_path_buf(),
+            capacity: capacity.max(1),
+            samples: VecDeque::new(),
+            system: System::new(),
+            pid: sysinfo::get_current_pid().ok(),
+        }
+    }
+
+    /// Nimmt eine neue Messung auf und liefert sie zurück
+    pub fn sample(&mut self) -> SystemSample {
+        let mut sample = SystemSample {
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            ..SystemSample::default()
+        };
+
+        if let Some(pid) = self.pid {
+            self.system.refresh_process_specifics(pid, ProcessRefreshKind::new().with_cpu());
+            if let Some(process) = self.system.process(pid) {
+                sample.rss_bytes = process.memory();
+                sample.cpu_percent = process.cpu_usage() as f64;
+            }
+        }
+
+        let load = self.system.load_average();
+        sample.load_average = [load.one, load.five, load.fifteen];
+        sample.free_disk_bytes = self.free_disk_space();
+        sample.open_file_descriptors = open_file_descriptors();
+
+        self.samples.push_back(sample.clone());
+        while self.samples.len() > self.capacity {
+            self.samples.pop_front();
+        }
+        sample
+    }
+
+    /// Die gespeicherte Zeitreihe, älteste Messung zuerst
+    pub fn samples(&self) -> Vec<SystemSample> {
+        self.samples.iter().cloned().collect()
+    }
+
+    pub fn latest(&self) -> Option<&SystemSample> {
+        self.samples.back()
+    }
+
+    /// Freier Platz auf dem Datenträger, dessen Einhängepunkt `volume` am genauesten enthält
+    fn free_disk_space(&mut self) -> u64 {
+        let volume = self.volume.canonicalize().unwrap_or_else(|_| self.volume.clone());
+        self.system.refresh_disks_list();
+        self.system
+            .disks()
+            .iter()
+            .filter(|disk| volume.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+            .map(|disk| disk.available_space())
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn open_file_descriptors() -> Option<u64> {
+    std::fs::read_dir("/proc/self/fd").ok().map(|entries| entries.count() as u64)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn open_file_descriptors() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_measures_own_process_and_keeps_window() {
+        let mut monitor = SystemMonitor::new(&std::env::temp_dir(), 2);
+        for _ in 0..3 {
+            monitor.sample();
+        }
+
+        let latest = monitor.latest().unwrap();
+        assert!(latest.rss_bytes > 0);
+        assert!(latest.free_disk_bytes > 0);
+        if cfg!(target_os = "linux") {
+            assert!(latest.open_file_descriptors.unwrap() >= 3);
+        }
+        assert_eq!(monitor.samples().len(), 2);
+    }
+}
@@ -0,0 +1,240 @@
+// src/transport.rs - Austauschbarer HTTP-Zugang für das Internet-Lernen
+//
+// Der Kern ruft Seiten nur über einen `Transport` ab, den er bei der Erzeugung erhält.
+// `HttpTransport` geht mit reqwest ins Netz; `ReplayTransport` liefert aufgezeichnete
+// Antworten aus einem Fixture-Verzeichnis und kann sie im Aufnahmemodus über einen
+// anderen Transport erst abrufen und ablegen. Je URL gibt es eine Datei
+// `<16 Zeichen SHA-256 der URL>.json` mit URL, Status, Kopfzeilen und Inhalt.
+use std::collections::BTreeMap;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+use serde::{Deserialize, Serialize};
+
+use crate::content_store::content_hash;
+use crate::crawler::CRAWLER_AGENT;
+
+/// Fehler eines Abrufs (Netzwerk, fehlende Aufzeichnung, ...)
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Ergebnis eines Abrufs
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<TransportResponse, TransportError>> + Send + 'a>>;
+
+/// Antwort auf eine GET-Anfrage; Namen der Kopfzeilen in Kleinbuchstaben
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransportResponse {
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl TransportResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    /// Inhalt als Text; ungültiges UTF-8 wird ersetzt
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Zugang des Kerns zum Netz
+pub trait Transport: Send + Sync {
+    /// Ruft `url` per GET mit zusätzlichen Kopfzeilen `headers` ab (z.B. `if-none-match`);
+    /// HTTP-Fehlerstatus sind eine Antwort, kein `Err`
+    fn get<'a>(&'a self, url: &'a str, headers: &'a BTreeMap<String, String>) -> TransportFuture<'a>;
+    fn name(&self) -> String;
+}
+
+/// Echte HTTP(S)-Anfragen über reqwest, ausgewiesen als `CRAWLER_AGENT`
+#[derive(Clone, Debug)]
+pub struct HttpTransport {
+    client: reqwest::Client,
+}
+
+impl HttpTransport {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent(format!("{}/{}", CRAWLER_AGENT, env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+        HttpTransport { client }
+    }
+}
+
+impl Default for HttpTransport {
+    fn default() -> Self {
+        HttpTransport::new()
+    }
+}
+
+impl Transport for HttpTransport {
+    fn get<'a>(&'a self, url: &'a str, headers: &'a BTreeMap<String, String>) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let mut request = self.client.get(url);
+            for (name, value) in headers {
+                request = request.header(name.as_str(), value.as_str());
+            }
+            let response = request.send().await?;
+            let status = response.status().as_u16();
+            let headers = response.headers().iter()
+                .filter_map(|(name, value)| Some((name.as_str().to_string(), value.to_str().ok()?.to_string())))
+                .collect();
+            let body = response.bytes().await?.to_vec();
+            Ok(TransportResponse { status, headers, body })
+        })
+    }
+
+    fn name(&self) -> String {
+        "http".to_string()
+    }
+}
+
+/// Eine aufgezeichnete Antwort
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Fixture {
+    url: String,
+    status: u16,
+    headers: BTreeMap<String, String>,
+    body: String,
+}
+
+/// Antworten aus `dir`; mit `recording` werden sie vorher über `inner` abgerufen und abgelegt.
+/// Bedingte Kopfzeilen werden beim Abspielen ignoriert, es gibt stets die ganze Aufzeichnung.
+/// Beim Aufnehmen werden `304`-Antworten nicht abgelegt, damit die Aufzeichnung vollständig bleibt.
+pub struct ReplayTransport {
+    pub dir: PathBuf,
+    inner: Option<Box<dyn Transport>>,
+}
+
+impl ReplayTransport {
+    /// Spielt nur ab; URLs ohne Aufzeichnung ergeben einen Fehler
+    pub fn new(dir: &Path) -> Self {
+        ReplayTransport { dir: dir.to_path_buf(), inner: None }
+    }
+
+    /// Ruft über `inner` ab und überschreibt die Aufzeichnung der URL
+    pub fn recording(dir: &Path, inner: Box<dyn Transport>) -> Self {
+        ReplayTransport { dir: dir.to_path_buf(), inner: Some(inner) }
+    }
+
+    /// Datei der Aufzeichnung von `url`
+    pub fn fixture_path(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", &content_hash(url.as_bytes())[..16]))
+    }
+
+    fn record(&self, url: &str, response: &TransportResponse) -> Result<(), TransportError> {
+        let fixture = Fixture {
+            url: url.to_string(),
+            status: response.status,
+            headers: response.headers.clone(),
+            body: response.text(),
+        };
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.fixture_path(url), serde_json::to_string_pretty(&fixture)?)?;
+        Ok(())
+    }
+
+    fn replay(&self, url: &str) -> Result<TransportResponse, TransportError> {
+        let path = self.fixture_path(url);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Keine Aufzeichnung für {} ({}: {})", url, path.display(), e))?;
+        let fixture: Fixture = serde_json::from_str(&content)?;
+        if fixture.url != url {
+            return Err(format!("{} enthält {} statt {}", path.display(), fixture.url, url).into());
+        }
+        Ok(TransportResponse { status: fixture.status, headers: fixture.headers, body: fixture.body.into_bytes() })
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn get<'a>(&'a self, url: &'a str, headers: &'a BTreeMap<String, String>) -> TransportFuture<'a> {
+        Box::pin(async move {
+            match &self.inner {
+                Some(inner) => {
+                    let response = inner.get(url, headers).await?;
+                    if response.status != 304 {
+                        self.record(url, &response)?;
+                    }
+                    Ok(response)
+                },
+                None => self.replay(url),
+            }
+        })
+    }
+
+    fn name(&self) -> String {
+        match self.inner {
+            Some(_) => format!("record({})", self.dir.display()),
+            None => format!("replay({})", self.dir.display()),
+        }
+    }
+}
+
+/// Transport nach `learning.transport`: "http", "replay" oder "record"
+pub fn transport_for(mode: &str, fixture_dir: &Path) -> Box<dyn Transport> {
+    match mode {
+        "replay" => Box::new(ReplayTransport::new(fixture_dir)),
+        "record" => Box::new(ReplayTransport::recording(fixture_dir, Box::new(HttpTransport::new()))),
+        _ => Box::new(HttpTransport::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feste Antwort ohne Netz
+    struct StaticTransport(TransportResponse);
+
+    impl Transport for StaticTransport {
+        fn get<'a>(&'a self, _url: &'a str, _headers: &'a BTreeMap<String, String>) -> TransportFuture<'a> {
+            let response = self.0.clone();
+            Box::pin(async move { Ok(response) })
+        }
+
+        fn name(&self) -> String {
+            "static".to_string()
+        }
+    }
+
+    #[test]
+    fn test_records_and_replays_responses() {
+        let dir = std::env::temp_dir().join(format!("evoli_fixtures_{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        let response = TransportResponse {
+            status: 200,
+            headers: BTreeMap::from([("content-type".to_string(), "text/html".to_string())]),
+            body: b"<html>fn a() {}</html>".to_vec(),
+        };
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        let none = BTreeMap::new();
+
+        let recorder = ReplayTransport::recording(&dir, Box::new(StaticTransport(response.clone())));
+        assert_eq!(rt.block_on(recorder.get("https://example.org/a", &none)).unwrap(), response);
+
+        let replay = ReplayTransport::new(&dir);
+        let replayed = rt.block_on(replay.get("https://example.org/a", &none)).unwrap();
+        assert_eq!(replayed, response);
+        assert_eq!(replayed.header("Content-Type"), Some("text/html"));
+        assert!(rt.block_on(replay.get("https://example.org/b", &none)).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_transport_for_selects_mode() {
+        let dir = Path::new("fixtures");
+        assert_eq!(transport_for("http", dir).name(), "http");
+        assert_eq!(transport_for("replay", dir).name(), "replay(fixtures)");
+        assert_eq!(transport_for("record", dir).name(), "record(fixtures)");
+    }
+}
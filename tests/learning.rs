@@ -0,0 +1,254 @@
+// tests/learning.rs - Internet-Lernen gegen einen lokalen HTTP-Server statt öffentlicher Seiten
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::thread;
+
+use enhanced_evoli_kern::{EnhancedEvoliKern, EvoliConfig, HttpTransport, ReplayTransport, Transport, TransportFuture};
+
+const PAGE: &str = "<html><body><h1>Beispiele</h1>\n```rust\nfn verdoppeln(x: i32) -> i32 {\n    x * 2\n}\n```\n</body></html>\n";
+
+/// Beantwortet Anfragen auf `routes` (Pfad, Inhalt) mit 200 und einem ETag, bei passendem
+/// `If-None-Match` mit 304, alles andere mit 404. Inhalte unter `/frisch` sind eine Stunde gültig.
+fn serve(routes: &[(&str, &str)]) -> String {
+    let routes: Vec<(String, String)> = routes.iter().map(|(path, body)| (path.to_string(), body.to_string())).collect();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0u8; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let request = String::from_utf8_lossy(&request).to_lowercase();
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let (status, body, etag) = match routes.iter().find(|(route, _)| route == path) {
+                Some((_, body)) => {
+                    let etag = format!("\"{}\"", body.len());
+                    if request.contains(&format!("if-none-match: {}", etag)) {
+                        ("304 Not Modified", "", etag)
+                    } else {
+                        ("200 OK", body.as_str(), etag)
+                    }
+                },
+                None => ("404 Not Found", "fehlt", String::new()),
+            };
+            let max_age = if path.starts_with("/frisch") { 3600 } else { 0 };
+            write!(stream, "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nETag: {}\r\nCache-Control: max-age={}\r\n\
+                            Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                   status, etag, max_age, body.len(), body).unwrap();
+        }
+    });
+    format!("http://{}", addr)
+}
+
+fn config(name: &str, url: &str) -> EvoliConfig {
+    let data_root = std::env::temp_dir().join(format!("evoli_learning_{}_{}", name, std::process::id()));
+    std::fs::remove_dir_all(&data_root).ok();
+
+    let mut config = EvoliConfig::default();
+    config.paths.project_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    config.paths.data_root = data_root;
+    config.learning.urls = vec![url.to_string()];
+    config.learning.min_request_interval_secs = 0;
+    config.learning.crawl.host_delay_secs = 0;
+    config
+}
+
+fn kernel(config: &EvoliConfig, transport: Box<dyn Transport>) -> EnhancedEvoliKern {
+    EnhancedEvoliKern::with_transport(config.clone(), transport).unwrap()
+}
+
+/// Netz ist nicht erreichbar
+struct Unreachable;
+
+impl Transport for Unreachable {
+    fn get<'a>(&'a self, url: &'a str, _headers: &'a BTreeMap<String, String>) -> TransportFuture<'a> {
+        Box::pin(async move { Err(format!("{} nicht erreichbar", url).into()) })
+    }
+
+    fn name(&self) -> String {
+        "unreachable".to_string()
+    }
+}
+
+#[tokio::test]
+async fn test_learns_from_local_server() {
+    let url = format!("{}/seite", serve(&[("/seite", PAGE)]));
+    let config = config("http", &url);
+    let mut kern = kernel(&config, Box::new(HttpTransport::new()));
+
+    kern.learn_from_internet().await.unwrap();
+
+    // Die fehlende robots.txt (404) zählt als Anfrage, nicht als Fehler
+    assert_eq!(kern.metrics().internet_requests, 2);
+    assert_eq!(kern.metrics().failed_internet_requests, 0);
+    assert_eq!(kern.metrics().bytes_downloaded, PAGE.len() as u64);
+
+    let cached: Vec<_> = kern.cache_store.entries.values().collect();
+    assert_eq!(cached.len(), 1);
+    assert_eq!(cached[0].url.as_deref(), Some(url.as_str()));
+    assert_eq!(cached[0].content_type.as_deref(), Some("text/html; charset=utf-8"));
+    assert_eq!(kern.cache_store.get_string(&cached[0].name).unwrap(), PAGE);
+
+    let knowledge: Vec<_> = kern.knowledge_store.entries.keys().cloned().collect();
+    assert_eq!(knowledge.len(), 1);
+    assert!(kern.knowledge_store.get_string(&knowledge[0]).unwrap().contains("fn verdoppeln(x: i32)"));
+
+    std::fs::remove_dir_all(&config.paths.data_root).ok();
+}
+
+#[tokio::test]
+async fn test_http_errors_are_counted_and_not_stored() {
+    let url = format!("{}/fehlt", serve(&[]));
+    let config = config("status", &url);
+    let mut kern = kernel(&config, Box::new(HttpTransport::new()));
+
+    kern.learn_from_internet().await.unwrap();
+
+    assert_eq!(kern.metrics().failed_internet_requests, 1);
+    assert!(kern.cache_store.entries.is_empty());
+    assert!(kern.knowledge_store.entries.is_empty());
+
+    std::fs::remove_dir_all(&config.paths.data_root).ok();
+}
+
+#[tokio::test]
+async fn test_recorded_session_replays_without_server() {
+    let url = format!("{}/seite", serve(&[("/seite", PAGE)]));
+    let fixtures = std::env::temp_dir().join(format!("evoli_learning_fixtures_{}", std::process::id()));
+    std::fs::remove_dir_all(&fixtures).ok();
+
+    // Aufnahme gegen den Server, einschließlich der (fehlenden) robots.txt
+    let recorded = config("record", &url);
+    let mut kern = kernel(&recorded, Box::new(ReplayTransport::recording(&fixtures, Box::new(HttpTransport::new()))));
+    kern.learn_from_internet().await.unwrap();
+    assert_eq!(kern.metrics().failed_internet_requests, 0);
+
+    // Wiedergabe in einer frischen Instanz liefert dasselbe Wissen
+    let replayed = config("replay", &url);
+    let mut replay = kernel(&replayed, Box::new(ReplayTransport::new(&fixtures)));
+    replay.learn_from_internet().await.unwrap();
+    assert_eq!(replay.metrics().failed_internet_requests, 0);
+    assert_eq!(replay.metrics().bytes_downloaded, kern.metrics().bytes_downloaded);
+
+    let hashes = |kern: &EnhancedEvoliKern| kern.knowledge_store.entries.values().map(|e| e.hash.clone()).collect::<Vec<_>>();
+    assert_eq!(hashes(&replay), hashes(&kern));
+
+    for dir in [&recorded.paths.data_root, &replayed.paths.data_root, &fixtures] {
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
+
+#[tokio::test]
+async fn test_crawls_links_and_respects_robots() {
+    let base = serve(&[
+        ("/robots.txt", "User-agent: *\nDisallow: /privat\n"),
+        ("/start", r#"<a href="/seite">Beispiele</a> <a href="/privat/notizen">privat</a> <a href="/start#oben">oben</a>"#),
+        ("/seite", PAGE),
+        ("/privat/notizen", PAGE),
+    ]);
+    let config = config("crawl", &format!("{}/start", base));
+    let mut kern = kernel(&config, Box::new(HttpTransport::new()));
+
+    // Startseite, dann der erlaubte Link; der gesperrte wird ohne Abruf übersprungen
+    kern.learn_from_internet().await.unwrap();
+    assert_eq!(kern.frontier.queue.len(), 2);
+    kern.learn_from_internet().await.unwrap();
+    kern.learn_from_internet().await.unwrap();
+
+    assert_eq!(kern.metrics().internet_requests, 3); // robots.txt, Startseite, erlaubter Link
+    assert!(kern.frontier.queue.is_empty());
+    assert!(kern.frontier.visited.contains(&format!("{}/privat/notizen", base)));
+    assert_eq!(kern.knowledge_store.entries.len(), 1);
+    let cached: Vec<_> = kern.cache_store.entries.values().filter_map(|entry| entry.url.clone()).collect();
+    assert_eq!(cached.len(), 2);
+    assert!(!cached.iter().any(|url| url.contains("privat")));
+
+    // Die Frontier überdauert einen Neustart
+    let resumed = kernel(&config, Box::new(HttpTransport::new()));
+    assert_eq!(resumed.frontier, kern.frontier);
+
+    std::fs::remove_dir_all(&config.paths.data_root).ok();
+}
+
+#[tokio::test]
+async fn test_robots_fetch_waits_for_host_delay() {
+    let base = serve(&[("/robots.txt", "User-agent: *\nDisallow:\n"), ("/seite", PAGE)]);
+    let mut config = config("robots_delay", &format!("{}/seite", base));
+    config.learning.crawl.host_delay_secs = 60;
+    let mut kern = kernel(&config, Box::new(HttpTransport::new()));
+
+    // Erst die robots.txt; die Seite wartet den Crawl-Delay des Hosts ab
+    kern.learn_from_internet().await.unwrap();
+    kern.learn_from_internet().await.unwrap();
+    assert_eq!(kern.metrics().internet_requests, 1);
+    assert_eq!(kern.frontier.queue.len(), 1);
+    assert!(kern.cache_store.entries.is_empty());
+
+    std::fs::remove_dir_all(&config.paths.data_root).ok();
+}
+
+#[tokio::test]
+async fn test_revalidates_stale_pages_and_reuses_fresh_ones() {
+    let base = serve(&[("/seite", PAGE), ("/frisch", PAGE)]);
+    let stale = config("revalidate", &format!("{}/seite", base));
+    let mut kern = kernel(&stale, Box::new(HttpTransport::new()));
+
+    // `max-age=0`: jede Runde fragt mit dem ETag nach und bekommt 304 (dazu einmal robots.txt)
+    kern.learn_from_internet().await.unwrap();
+    kern.learn_from_internet().await.unwrap();
+    assert_eq!(kern.metrics().internet_requests, 3);
+    assert_eq!(kern.metrics().not_modified, 1);
+    assert_eq!(kern.metrics().bytes_downloaded, PAGE.len() as u64);
+    let entry = kern.cache_store.entries.values().next().unwrap().clone();
+    assert_eq!(entry.http.unwrap().etag.as_deref(), Some(format!("\"{}\"", PAGE.len()).as_str()));
+
+    // Eine Stunde frisch: die zweite Runde kommt ohne Anfrage aus
+    let fresh = config("fresh", &format!("{}/frisch", base));
+    let mut kern = kernel(&fresh, Box::new(HttpTransport::new()));
+    kern.learn_from_internet().await.unwrap();
+    kern.learn_from_internet().await.unwrap();
+    assert_eq!(kern.metrics().internet_requests, 2);
+    assert_eq!(kern.metrics().cache_hits, 1);
+
+    for dir in [&stale.paths.data_root, &fresh.paths.data_root] {
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
+
+#[tokio::test]
+async fn test_learns_offline_from_preseeded_cache() {
+    let url = format!("{}/seite", serve(&[("/seite", PAGE)]));
+    let mut config = config("offline", &url);
+    kernel(&config, Box::new(HttpTransport::new())).learn_from_internet().await.unwrap();
+
+    // Ohne Netz: die veraltete Kopie aus dem Cache dient als Ersatz
+    std::fs::remove_dir_all(config.paths.knowledge_path()).ok();
+    let mut kern = kernel(&config, Box::new(Unreachable));
+    kern.learn_from_internet().await.unwrap();
+    assert_eq!(kern.metrics().failed_internet_requests, 1);
+    assert_eq!(kern.metrics().cache_hits, 1);
+    assert_eq!(kern.knowledge_store.entries.len(), 1);
+
+    // Abgeschottete Maschine: nur der Cache wird übernommen, ohne Startseiten und ohne Anfragen
+    std::fs::remove_dir_all(config.paths.knowledge_path()).ok();
+    std::fs::remove_dir_all(config.paths.state_path()).ok();
+    config.learning.offline = true;
+    config.learning.urls.clear();
+    let mut kern = kernel(&config, Box::new(Unreachable));
+    kern.learn_from_internet().await.unwrap();
+    assert_eq!(kern.metrics().internet_requests, 0);
+    assert_eq!(kern.metrics().cache_hits, 1);
+    assert_eq!(kern.knowledge_store.entries.len(), 1);
+    assert!(kern.frontier.visited.contains(&url));
+
+    std::fs::remove_dir_all(&config.paths.data_root).ok();
+}